@@ -0,0 +1,35 @@
+// small random movements so that servers don't kick the bot for being idle
+
+use rand::Rng;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Action {
+    Look(f32, f32, f32), // yaw, pitch, for how many seconds before looking back
+    Swing,
+    Jump,
+    Step(f64, f64), // velocity x, z to step with before stepping back
+}
+
+// how long to wait before the next action, in seconds
+pub fn next_delay() -> f32 {
+    rand::thread_rng().gen_range(20.0, 60.0)
+}
+
+pub fn random_action(rotation: (f32, f32)) -> Action {
+    let (yaw, pitch) = rotation;
+    let mut rng = rand::thread_rng();
+
+    match rng.gen_range(0, 4) {
+        0 => Action::Look(
+            yaw + rng.gen_range(-40.0, 40.0),
+            (pitch + rng.gen_range(-20.0, 20.0)).clamp(-90.0, 90.0),
+            rng.gen_range(0.5, 2.0),
+        ),
+        1 => Action::Swing,
+        2 => Action::Jump,
+        _ => {
+            let angle = rng.gen_range(0.0, std::f64::consts::PI * 2.0);
+            Action::Step(angle.cos() * 0.1, angle.sin() * 0.1)
+        }
+    }
+}
@@ -0,0 +1,478 @@
+// a small HTTP server for watching and controlling the bots from scripts and dashboards
+// off by default, every request needs the token from the config as "Authorization: Bearer <token>",
+// or as ?token=<token> for browsers, which can't set headers on WebSockets
+//
+//   GET    /bots                    the running bots with their status
+//   POST   /bots                    start one, with {"profile", "address", "username"}, all optional
+//   GET    /bots/<id>               its status and settings
+//   DELETE /bots/<id>               disconnect it
+//   GET    /bots/<id>/statistics    what changed since connecting
+//   GET    /bots/<id>/inventory     the hotbar
+//   GET    /bots/<id>/mobs          the mobs around it, nearest first
+//   PATCH  /bots/<id>/settings      change some of the settings, e.g. {"sneak": true}
+//   GET    /metrics                 the numbers of all the bots for Prometheus
+//   GET    /events                  a WebSocket with the events of all the bots as JSON, or of one with ?bot=<id>
+
+use crate::clone_all;
+use crate::config::{Config, Profile};
+use crate::datatypes::Slot;
+use crate::handle::Event;
+use crate::http::{self, Request};
+use crate::manager::{Manager, Session};
+use crate::metrics;
+use crate::mobs::MOBS;
+use crate::registries;
+use crate::schedule;
+use crate::websocket;
+use crate::{Bot, Disconnect, Settings};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::io;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiSettings {
+    pub enabled: bool,
+    pub address: String, // to listen on, only reachable from this machine by default
+    pub token: String,
+}
+
+impl Default for ApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: "127.0.0.1:25590".to_string(),
+            token: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ConnectRequest {
+    pub profile: Option<String>, // the one that was used last if not given
+    pub address: Option<String>, // instead of the profile's
+    pub username: Option<String>,
+}
+
+impl ConnectRequest {
+    // the name of the profile to use, and the profile with the request's changes
+    pub fn resolve(&self, config: &Config) -> Result<(String, Profile), String> {
+        let name = match &self.profile {
+            Some(name) => name.clone(),
+            None => config.current_profile.clone(),
+        };
+        let mut profile = match config.profiles.get(&name) {
+            Some(profile) => profile.clone(),
+            // without any profiles, the address and username are enough
+            None if self.profile.is_none() => Profile::default(),
+            None => return Err(format!("There's no profile {:?}", name)),
+        };
+        if let Some(address) = &self.address {
+            profile.address = address.clone();
+        }
+        if let Some(username) = &self.username {
+            profile.username = username.clone();
+        }
+        if profile.address.is_empty() || profile.username.is_empty() {
+            return Err(format!(
+                "Profile {:?} has no address or username, give them in the request",
+                name
+            ));
+        }
+        Ok((name, profile))
+    }
+}
+
+// starts a bot the same way as the app does, so that it shows up like the others, and returns its id
+pub type Connect = Arc<
+    dyn Fn(ConnectRequest) -> Pin<Box<dyn Future<Output = Result<u32, String>> + Send>>
+        + Send
+        + Sync,
+>;
+
+#[derive(Clone)]
+struct Api {
+    token: String,
+    manager: Arc<std::sync::Mutex<Manager>>,
+    connect: Connect,
+}
+
+// runs until the program exits
+pub async fn serve<W: FnMut(String) + Clone + Send + 'static>(
+    settings: ApiSettings,
+    manager: Arc<std::sync::Mutex<Manager>>,
+    connect: Connect,
+    mut write_to_log: W,
+) {
+    if settings.token.is_empty() {
+        write_to_log(
+            "The API is enabled but has no token, set one in the config file.".to_string(),
+        );
+        return;
+    }
+    let listener = match TcpListener::bind(&settings.address).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            write_to_log(format!(
+                "Couldn't start the API on {}: {}",
+                settings.address, e
+            ));
+            return;
+        }
+    };
+    write_to_log(format!("API listening on http://{}", settings.address));
+
+    let api = Api {
+        token: settings.token,
+        manager,
+        connect,
+    };
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn({
+                    clone_all![api];
+                    async move {
+                        // nothing to do if the client went away
+                        let _ = handle(stream, api).await;
+                    }
+                });
+            }
+            Err(e) => write_to_log(format!("API: {}", e)),
+        }
+    }
+}
+
+async fn handle(mut stream: TcpStream, api: Api) -> io::Result<()> {
+    let request = match http::read_request(&mut stream).await {
+        Ok(request) => request,
+        Err(e) => {
+            return http::write_response(&mut stream, 400, &error(e.to_string()).to_string()).await
+        }
+    };
+    let (status, body) = if !authorized(&request, &api.token) {
+        (401, error("Missing or wrong token".to_string()))
+    } else if request.path.trim_matches('/') == "events" {
+        return feed(stream, request, api).await;
+    } else if request.path.trim_matches('/') == "metrics" && request.method == "GET" {
+        let body = metrics::render(&api.manager).await;
+        // the version of the text format
+        let content_type = "text/plain; version=0.0.4";
+        return http::write_response_with_type(&mut stream, 200, content_type, &body).await;
+    } else {
+        route(&api, request).await
+    };
+    http::write_response(&mut stream, status, &body.to_string()).await
+}
+
+fn authorized(request: &Request, token: &str) -> bool {
+    let given = match (request.header("authorization"), request.query("token")) {
+        (Some(header), _) => header.strip_prefix("Bearer ").unwrap_or(""),
+        (None, Some(token)) => token,
+        (None, None) => "",
+    };
+    // compared in constant time, so that the token can't be guessed from how long it takes
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+async fn route(api: &Api, request: Request) -> (u16, Value) {
+    let path: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    let method = request.method.as_str();
+    match path.as_slice() {
+        ["bots"] => match method {
+            "GET" => {
+                let manager_lock = api.manager.lock().unwrap();
+                let bots: Vec<Value> = manager_lock
+                    .iter()
+                    .map(|(id, session)| session_json(id, session))
+                    .collect();
+                (200, Value::Array(bots))
+            }
+            "POST" => {
+                let connect_request = if request.body.is_empty() {
+                    ConnectRequest::default()
+                } else {
+                    match serde_json::from_slice(&request.body) {
+                        Ok(connect_request) => connect_request,
+                        Err(e) => return (400, error(format!("Bad request body: {}", e))),
+                    }
+                };
+                match (api.connect)(connect_request).await {
+                    Ok(id) => (201, json!({ "id": id })),
+                    Err(e) => (400, error(e)),
+                }
+            }
+            _ => (405, error("Use GET or POST".to_string())),
+        },
+        ["bots", id, rest @ ..] => {
+            let id = id.parse::<u32>().ok();
+            let found = id.and_then(|id| {
+                let manager_lock = api.manager.lock().unwrap();
+                manager_lock.get(id).map(|session| {
+                    let statistics = json!(session.statistics);
+                    (session_json(id, session), session.bot.clone(), statistics)
+                })
+            });
+            let (session, bot, statistics) = match found {
+                Some(found) => found,
+                None => return (404, error("There's no such bot".to_string())),
+            };
+            match (method, rest) {
+                ("GET", []) => {
+                    let mut session = session;
+                    session["settings"] = settings_json(&*bot.settings().lock().await);
+                    (200, session)
+                }
+                ("DELETE", []) => {
+                    bot.disconnect().await;
+                    (202, json!({}))
+                }
+                ("GET", ["statistics"]) => (200, statistics),
+                ("GET", ["inventory"]) => (200, inventory(&bot).await),
+                ("GET", ["mobs"]) => (200, mobs(&bot).await),
+                ("PATCH", ["settings"]) => change_settings(&bot, &request.body).await,
+                (_, [])
+                | (_, ["statistics"])
+                | (_, ["inventory"])
+                | (_, ["mobs"])
+                | (_, ["settings"]) => (405, error("Wrong method".to_string())),
+                _ => (404, error("Not found".to_string())),
+            }
+        }
+        _ => (404, error("Not found".to_string())),
+    }
+}
+
+// sends the events as text frames until the client closes the connection
+async fn feed(mut stream: TcpStream, request: Request, api: Api) -> io::Result<()> {
+    if request.method != "GET" {
+        return http::write_response(&mut stream, 405, &error("Use GET".to_string()).to_string())
+            .await;
+    }
+    let key = match websocket::key(&request) {
+        Some(key) => key.to_string(),
+        None => {
+            let body = error("Connect to it with a WebSocket".to_string());
+            return http::write_response(&mut stream, 400, &body.to_string()).await;
+        }
+    };
+    let only = match request.query("bot").map(|id| id.parse::<u32>()) {
+        Some(Ok(id)) => Some(id),
+        Some(Err(_)) => {
+            let body = error("The bot should be an id".to_string());
+            return http::write_response(&mut stream, 400, &body.to_string()).await;
+        }
+        None => None,
+    };
+
+    // subscribed before answering, so that nothing is missed after the client is told it's connected
+    let mut events = api.manager.lock().unwrap().subscribe();
+    websocket::accept(&mut stream, &key).await?;
+
+    // the client's frames are read in their own task, so that a frame is never left half-read
+    let (mut reader, mut writer) = stream.into_split();
+    let (replies, mut replies_receiver) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        loop {
+            match websocket::read_frame(&mut reader).await {
+                Ok((websocket::PING, payload)) => {
+                    if replies.send((websocket::PONG, payload)).is_err() {
+                        return;
+                    }
+                }
+                // nothing else is expected from the client
+                Ok((websocket::CLOSE, payload)) => {
+                    let _ = replies.send((websocket::CLOSE, payload));
+                    return;
+                }
+                Ok(_) => {}
+                Err(_) => {
+                    let _ = replies.send((websocket::CLOSE, Vec::new()));
+                    return;
+                }
+            }
+        }
+    });
+
+    loop {
+        tokio::select! {
+            reply = replies_receiver.recv() => match reply {
+                Some((opcode, payload)) => {
+                    websocket::write_frame(&mut writer, opcode, &payload).await?;
+                    if opcode == websocket::CLOSE {
+                        return Ok(());
+                    }
+                }
+                None => return Ok(()),
+            },
+            event = events.recv() => match event {
+                Ok((id, event)) => {
+                    if only.is_none_or(|only| only == id) {
+                        let text = event_json(id, &event).to_string();
+                        websocket::write_frame(&mut writer, websocket::TEXT, text.as_bytes()).await?;
+                    }
+                }
+                // a slow client misses some events rather than holding up the bots
+                Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => return Ok(()),
+            },
+        }
+    }
+}
+
+fn event_json(id: u32, event: &Event) -> Value {
+    let mut value = match event {
+        Event::Log(level, message) => json!({
+            "type": "log",
+            "level": level.to_string().to_lowercase(),
+            "message": message
+        }),
+        Event::HealthChanged { health, food } => {
+            json!({ "type": "health", "health": health, "food": food })
+        }
+        Event::MobKilled { mob, count, total } => {
+            json!({ "type": "kill", "mob": mob, "count": count, "total": total })
+        }
+        Event::ChatReceived(message) => json!({ "type": "chat", "message": message }),
+        Event::Kicked(reason) => json!({ "type": "kicked", "reason": reason }),
+        Event::Died { message, pos } => json!({
+            "type": "died",
+            "message": message,
+            "x": pos.0,
+            "y": pos.1,
+            "z": pos.2,
+        }),
+        Event::ItemBroken(item) => json!({ "type": "item_broken", "item": item }),
+        Event::PlayerNearby { name, distance } => {
+            json!({ "type": "player_nearby", "name": name, "distance": distance })
+        }
+        Event::StatisticsUpdated { rows, .. } => json!({ "type": "statistics", "rows": rows }),
+        Event::Disconnected(disconnect) => {
+            let reason = match disconnect {
+                Disconnect::Requested => "requested",
+                Disconnect::LowHealth => "low_health",
+                Disconnect::Error => "error",
+            };
+            json!({ "type": "disconnected", "reason": reason })
+        }
+    };
+    value["bot"] = json!(id);
+    value
+}
+
+fn error(message: String) -> Value {
+    json!({ "error": message })
+}
+
+fn session_json(id: u32, session: &Session) -> Value {
+    json!({
+        "id": id,
+        "address": session.address,
+        "username": session.username,
+        // null until the server sends the health
+        "status": session.status,
+    })
+}
+
+fn settings_json(settings: &Settings) -> Value {
+    let mut value = serde_json::to_value(settings).unwrap();
+    // they can be changed, but not read
+    value.as_object_mut().unwrap().remove("login_passwords");
+    value
+}
+
+async fn inventory(bot: &Bot) -> Value {
+    let state_lock = bot.state().lock().await;
+    let hotbar: Vec<Value> = state_lock
+        .hotbar
+        .iter()
+        .enumerate()
+        .filter_map(|(slot, item)| match item {
+            Slot::Present(id, count) => Some(json!({
+                "slot": slot + 1,
+                "id": id.0,
+                "name": registries::item_name(id.0),
+                "count": count,
+            })),
+            Slot::NotPresent => None,
+        })
+        .collect();
+    json!({
+        "held_slot": state_lock.held_item + 1,
+        "hotbar": hotbar,
+    })
+}
+
+async fn mobs(bot: &Bot) -> Value {
+    let state_lock = bot.state().lock().await;
+    let my_pos = state_lock.my_pos;
+    let mut mobs: Vec<(f64, Value)> = state_lock
+        .mobs
+        .values()
+        .map(|mob| {
+            let distance = ((my_pos.0 - mob.pos.0).powi(2)
+                + (my_pos.1 - mob.pos.1).powi(2)
+                + (my_pos.2 - mob.pos.2).powi(2))
+            .sqrt();
+            let name = match MOBS.get_by_left(&mob.entity_type.0) {
+                Some(name) => name.to_string(),
+                None => format!("#{}", mob.entity_type.0),
+            };
+            let value = json!({
+                "id": mob.entity_id.0,
+                "type": name,
+                "x": mob.pos.0,
+                "y": mob.pos.1,
+                "z": mob.pos.2,
+                "distance": distance,
+            });
+            (distance, value)
+        })
+        .collect();
+    mobs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    Value::Array(mobs.into_iter().map(|(_, value)| value).collect())
+}
+
+// the changes are merged into the current settings, which are then checked as a whole
+async fn change_settings(bot: &Bot, body: &[u8]) -> (u16, Value) {
+    let changes = match serde_json::from_slice(body) {
+        Ok(Value::Object(changes)) => changes,
+        Ok(_) => return (400, error("Expected a JSON object".to_string())),
+        Err(e) => return (400, error(format!("Bad request body: {}", e))),
+    };
+
+    let mut settings_lock = bot.settings().lock().await;
+    let mut value = serde_json::to_value(&*settings_lock).unwrap();
+    for (key, new) in changes {
+        match value.get_mut(&key) {
+            Some(old) => *old = new,
+            None => return (400, error(format!("There's no setting {:?}", key))),
+        }
+    }
+    let mut settings: Settings = match serde_json::from_value(value) {
+        Ok(settings) => settings,
+        Err(e) => return (400, error(format!("Bad settings: {}", e))),
+    };
+    let (scheduled_messages, errors) = schedule::parse(&settings.schedule);
+    if !errors.is_empty() {
+        return (400, error(format!("Bad schedule: {}", errors.join(", "))));
+    }
+    // these aren't part of the JSON
+    settings.scheduled_messages = scheduled_messages;
+    settings.goto_now = settings_lock.goto_now;
+
+    *settings_lock = settings;
+    (200, settings_json(&settings_lock))
+}
@@ -3,7 +3,7 @@ use core::future::poll_fn;
 use core::task::Poll;
 use flate2::read::ZlibDecoder;
 use flate2::write::ZlibEncoder;
-use std::io::Write;
+use std::io::{Cursor, Read, Write};
 use tokio::io::{self, AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
 use tokio_io::{AsyncRead, AsyncWrite};
@@ -79,12 +79,32 @@ impl MyAsyncRead for TcpStream {
 #[async_trait]
 impl<T: AsyncRead + Send> MyAsyncRead for ZlibDecoder<T> {
     async fn read(&mut self, output: &mut [u8]) -> io::Result<()> {
-        poll_fn(|_| match self.poll_read(output) {
-            Ok(futures::Async::Ready(t)) => Poll::Ready(Ok(t)),
-            Ok(futures::Async::NotReady) => Poll::Pending,
-            Err(e) => Poll::Ready(Err(e)),
-        })
-        .await?;
+        // the decoder can return less bytes than asked for, so keep reading until the buffer is full
+        let mut read = 0;
+        while read < output.len() {
+            let n = poll_fn(|_| match self.poll_read(&mut output[read..]) {
+                Ok(futures::Async::Ready(t)) => Poll::Ready(Ok(t)),
+                Ok(futures::Async::NotReady) => Poll::Pending,
+                Err(e) => Poll::Ready(Err(e)),
+            })
+            .await?;
+            if n == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "compressed packet ended too early",
+                ));
+            }
+            read += n;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MyAsyncRead for Cursor<Vec<u8>> {
+    async fn read(&mut self, output: &mut [u8]) -> io::Result<()> {
+        Read::read_exact(self, output)?;
 
         Ok(())
     }
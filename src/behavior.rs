@@ -0,0 +1,115 @@
+// custom AFK routines, run alongside the bot's own tasks
+// every behavior gets its own task, so a hook that takes a while only holds up that behavior
+
+use crate::bot::State;
+use crate::client::Client;
+use crate::handle::Event;
+use crate::packets::{ClientBound, ServerBound};
+use crate::Settings;
+use async_trait::async_trait;
+use log::Level;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io;
+use tokio::sync::{mpsc, Mutex, MutexGuard};
+
+// an error returned from a hook disconnects the bot, like any other connection error
+#[async_trait]
+pub trait Behavior: Send {
+    // shown in the log, and used to turn the built-in behaviors on in the settings
+    fn name(&self) -> &str;
+
+    // once the server has let the bot into the game
+    async fn on_join(&mut self, _context: &mut Context) -> io::Result<()> {
+        Ok(())
+    }
+
+    // every tick (50 ms) after joining
+    async fn on_tick(&mut self, _context: &mut Context) -> io::Result<()> {
+        Ok(())
+    }
+
+    // every packet from the server, in the order they came in
+    async fn on_packet(&mut self, _context: &mut Context, _packet: &ClientBound) -> io::Result<()> {
+        Ok(())
+    }
+
+    // when the bot stops, after all the other hooks, so nothing should be sent anymore
+    async fn on_disconnect(&mut self, _context: &mut Context) {}
+}
+
+// what the hooks can use
+pub struct Context {
+    pub(crate) state: Arc<Mutex<State>>,
+    pub(crate) client: Arc<Mutex<Client>>,
+    pub(crate) settings: Arc<Mutex<Settings>>,
+    pub(crate) events: mpsc::UnboundedSender<Event>,
+    pub(crate) address: String, // of the server
+}
+
+impl Context {
+    // don't hold on to it for long, the other tasks need it too
+    pub async fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().await
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub async fn settings(&self) -> MutexGuard<'_, Settings> {
+        self.settings.lock().await
+    }
+
+    pub async fn send(&self, packet: ServerBound) -> io::Result<()> {
+        self.client.lock().await.send(packet).await
+    }
+
+    pub fn log(&self, message: String) {
+        // fails only if nobody is listening to the events anymore
+        let _ = self.events.send(Event::Log(Level::Info, message));
+    }
+
+    // for what went wrong but doesn't stop the bot
+    pub fn warn(&self, message: String) {
+        let _ = self.events.send(Event::Log(Level::Warn, message));
+    }
+}
+
+pub(crate) enum Hook {
+    Join,
+    Packet(Arc<ClientBound>), // shared, since every behavior gets the same packets
+    Disconnect,
+}
+
+// calls the hooks of a behavior until the bot disconnects or one of them fails
+pub(crate) async fn run(
+    behavior: &mut Box<dyn Behavior>,
+    context: &mut Context,
+    hooks: &mut mpsc::UnboundedReceiver<Hook>,
+) -> io::Result<()> {
+    let mut ticks = tokio::time::interval(Duration::from_millis(50));
+    let mut joined = false;
+    loop {
+        tokio::select! {
+            hook = hooks.recv() => match hook {
+                Some(Hook::Join) => {
+                    joined = true;
+                    behavior.on_join(context).await?;
+                }
+                Some(Hook::Packet(packet)) => behavior.on_packet(context, &packet).await?,
+                Some(Hook::Disconnect) | None => return Ok(()),
+            },
+            _ = ticks.tick(), if joined => behavior.on_tick(context).await?,
+        }
+    }
+}
+
+// after a hook has failed the bot is already disconnecting, so this only waits for it to finish
+pub(crate) async fn wait_for_disconnect(hooks: &mut mpsc::UnboundedReceiver<Hook>) {
+    while let Some(hook) = hooks.recv().await {
+        if let Hook::Disconnect = hook {
+            return;
+        }
+    }
+}
@@ -0,0 +1,237 @@
+// the behaviors that come with the bot, each one can be turned off in the settings
+
+use crate::behavior::{Behavior, Context};
+use crate::bot::{self, Mob};
+use crate::datatypes::*;
+use crate::packets::ServerBound;
+use async_trait::async_trait;
+use std::time::{Duration, Instant};
+use tokio::io;
+
+// name, description
+pub const BUILT_IN: [(&str, &str); 3] = [
+    ("sneak", "Sneak and hold the weapon"),
+    ("eat", "Eat when hungry"),
+    ("attack", "Attack nearby mobs"),
+];
+
+pub fn built_in(name: &str) -> Option<Box<dyn Behavior>> {
+    match name {
+        "sneak" => Some(Box::new(Sneak)),
+        "eat" => Some(Box::new(Eat::default())),
+        "attack" => Some(Box::new(Attack::default())),
+        _ => None,
+    }
+}
+
+// starts/stops sneaking and changes the held item, depending on the settings in real time
+pub struct Sneak;
+
+#[async_trait]
+impl Behavior for Sneak {
+    fn name(&self) -> &str {
+        "sneak"
+    }
+
+    async fn on_tick(&mut self, context: &mut Context) -> io::Result<()> {
+        let mut state_lock = context.state().await;
+        let settings_lock = context.settings().await;
+        // change hotbar item if needed
+        if state_lock.held_item != settings_lock.weapon {
+            context
+                .send(ServerBound::HeldItemChange(settings_lock.weapon as i16))
+                .await?;
+            state_lock.held_item = settings_lock.weapon;
+        }
+
+        // sneaking things
+        if let Some(my_entity_id) = state_lock.my_entity_id {
+            let settings_sneaking = settings_lock.sneak;
+            if settings_sneaking == state_lock.sneaking {
+                return Ok(());
+            }
+            if settings_sneaking {
+                // start sneaking
+                context
+                    .send(ServerBound::EntityAction(
+                        VarInt(my_entity_id.0),
+                        VarInt(0),
+                        VarInt(0),
+                    ))
+                    .await?;
+            } else {
+                // stop sneaking
+                context
+                    .send(ServerBound::EntityAction(
+                        VarInt(my_entity_id.0),
+                        VarInt(1),
+                        VarInt(1),
+                    ))
+                    .await?;
+            }
+            state_lock.sneaking = settings_sneaking;
+        }
+        Ok(())
+    }
+}
+
+// eats food from the hotbar when hungry, or right away when asked to
+#[derive(Default)]
+pub struct Eat {
+    last_check: Option<Instant>,
+}
+
+#[async_trait]
+impl Behavior for Eat {
+    fn name(&self) -> &str {
+        "eat"
+    }
+
+    async fn on_tick(&mut self, context: &mut Context) -> io::Result<()> {
+        // checking every second is plenty
+        if let Some(last_check) = self.last_check {
+            if last_check.elapsed() < Duration::from_secs(1) {
+                return Ok(());
+            }
+        }
+        self.last_check = Some(Instant::now());
+
+        let (slot, eat_now) = {
+            let mut state_lock = context.state().await;
+            let settings_lock = context.settings().await;
+
+            let eat_now = state_lock.eat_now;
+            state_lock.eat_now = false;
+            if state_lock.food >= settings_lock.eat_at && !eat_now {
+                return Ok(());
+            }
+            // gotta eat something
+            // find food in hotbar that's allowed to be eaten
+            let slot = (0..9).find(|&slot_id| match &state_lock.hotbar[slot_id] {
+                Slot::Present(id, _number) => settings_lock.eat_food.contains(&id.0),
+                Slot::NotPresent => false,
+            });
+            (slot, eat_now)
+        };
+
+        match slot {
+            Some(slot_id) => {
+                context.log("Eating.".to_string());
+                context
+                    .send(ServerBound::HeldItemChange(slot_id as i16))
+                    .await?;
+                context.send(ServerBound::UseItem(VarInt(0))).await?;
+                // eating takes 32 ticks (1.6 s), finishing before the server gets there cancels it,
+                // so wait 2 more ticks in case the server is lagging behind
+                tokio::time::sleep(Duration::from_millis(34 * 50)).await;
+                context
+                    .send(ServerBound::PlayerDigging(VarInt(5), 0, 0))
+                    .await?;
+                let weapon = context.settings().await.weapon;
+                context
+                    .send(ServerBound::HeldItemChange(weapon as i16))
+                    .await?;
+            }
+            None if eat_now => context.log("Nothing to eat in the hotbar.".to_string()),
+            None => {}
+        }
+        Ok(())
+    }
+}
+
+// attacks the nearest of the selected mobs within reach, as often as the attack speed allows
+#[derive(Default)]
+pub struct Attack {
+    next_attack: Option<Instant>,
+    hand: i64, // the arm to swing, alternating
+}
+
+#[async_trait]
+impl Behavior for Attack {
+    fn name(&self) -> &str {
+        "attack"
+    }
+
+    async fn on_tick(&mut self, context: &mut Context) -> io::Result<()> {
+        let attack_speed = context.settings().await.attack_speed;
+        if attack_speed == 0.0 {
+            // skip the attack
+            return Ok(());
+        }
+        let now = Instant::now();
+        if let Some(next_attack) = self.next_attack {
+            if now < next_attack {
+                return Ok(());
+            }
+        }
+        self.next_attack = Some(now + Duration::from_secs_f32(1.0 / attack_speed));
+
+        // calculate the nearest mob
+        let mut state_lock = context.state().await;
+        let settings_lock = context.settings().await;
+        let line_of_sight = settings_lock
+            .line_of_sight_servers
+            .contains(context.address());
+        let my_pos = state_lock.my_pos;
+        let sq_dist = |mob: &Mob| {
+            (my_pos.0 - mob.pos.0).powi(2)
+                + (my_pos.1 - mob.pos.1).powi(2)
+                + (my_pos.2 - mob.pos.2).powi(2)
+        };
+        let nearest = state_lock
+            .mobs
+            .iter()
+            .filter(|(_, mob)| settings_lock.attack_mobs.contains(&mob.entity_type.0))
+            // hitting through walls gets flagged by anticheats
+            .filter(|(_, mob)| !line_of_sight || bot::can_see(&state_lock, mob))
+            .map(|(id, mob)| (*id, sq_dist(mob)))
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        let sneaking = settings_lock.sneak;
+        drop(settings_lock);
+
+        // if a mob was found, attack it
+        let nearest_mob_id = match nearest {
+            Some((id, squared_distance)) if squared_distance < 16.0 => id,
+            _ => return Ok(()),
+        };
+
+        // change the player rotation to look at the mob
+        // this is not neccessary but helps when debugging
+        // because you can see what mob the bot is trying to attack
+        //
+        // calculate pitch and yaw
+        let mob = &state_lock.mobs[&nearest_mob_id];
+        let dx = mob.pos.0 - my_pos.0;
+        let dy = mob.pos.1 - my_pos.1;
+        let dz = mob.pos.2 - my_pos.2;
+        let r = (dx * dx + dy * dy + dz * dz).sqrt();
+        let mut yaw = -dx.atan2(dz) / std::f64::consts::PI * 180.0;
+        if yaw < 0.0 {
+            yaw += 360.0;
+        }
+        let pitch = -(dy / r).asin() / std::f64::consts::PI * 180.0;
+        state_lock.my_rotation = (yaw as f32, pitch as f32);
+        context
+            .send(ServerBound::PlayerRotation(
+                yaw as f32,
+                pitch as f32,
+                state_lock.on_ground,
+            ))
+            .await?;
+
+        // attack the mob
+        context
+            .send(ServerBound::InteractEntity(
+                nearest_mob_id,
+                VarInt(1),
+                sneaking,
+            ))
+            .await?;
+        // also animation
+        context
+            .send(ServerBound::Animation(VarInt(self.hand)))
+            .await?;
+        self.hand = (self.hand + 1) % 2;
+        Ok(())
+    }
+}
@@ -0,0 +1,337 @@
+// properties of block state IDs
+// the IDs are only valid for protocol 754 (1.16.5), other versions number them differently
+//
+// only the blocks that are common around farms are known, everything else is treated
+// as a solid full block, which is the safe choice both for physics and line of sight
+// likewise, only the partial blocks common around farms have their own collision shape
+
+// first state, last state, name
+const PASSABLE: &[(u32, u32, &str)] = &[
+    (0, 0, "Air"),
+    (21, 32, "Saplings"),
+    (34, 49, "Water"),
+    (50, 65, "Lava"),
+    (1305, 1328, "Powered and Detector Rails"),
+    (1341, 1341, "Cobweb"),
+    (1342, 1344, "Grass, Fern and Dead Bush"),
+    (1345, 1347, "Seagrass"),
+    (1412, 1424, "Flowers"),
+    (1425, 1426, "Mushrooms"),
+    (1435, 1439, "Torches"),
+    (1440, 1952, "Fire"),
+    (2058, 3353, "Redstone Wire"),
+    (3357, 3364, "Wheat"),
+    (3381, 3572, "Signs"),
+    (9669, 9670, "Void and Cave Air"),
+    (9671, 9672, "Bubble Column"),
+];
+
+// whether entities can move and see through the block
+pub fn is_passable(state: u32) -> bool {
+    PASSABLE
+        .iter()
+        .any(|&(first, last, _)| state >= first && state <= last)
+}
+
+#[derive(Clone, Copy)]
+enum Shape {
+    // covers the whole block up to this many sixteenths
+    Height(u8),
+    Chest,
+    Hopper,
+    Slab,
+    SnowLayers,
+    Stairs,
+    Trapdoor,
+}
+
+// first state, last state, shape, name
+// the blocks sharing a range all have the same states, so the state's properties can be
+// found from its offset in the range
+const SHAPES: &[(u32, u32, Shape, &str)] = &[
+    (1049, 1304, Shape::Height(9), "Beds"),
+    (1954, 2033, Shape::Stairs, "Oak Stairs"),
+    (2034, 2057, Shape::Chest, "Chest"),
+    (3365, 3372, Shape::Height(15), "Farmland"),
+    (3655, 3734, Shape::Stairs, "Cobblestone Stairs"),
+    (3921, 3928, Shape::SnowLayers, "Snow"),
+    (4000, 4000, Shape::Height(14), "Soul Sand"),
+    (4111, 4494, Shape::Trapdoor, "Wooden Trapdoors"),
+    (4856, 5015, Shape::Stairs, "Brick and Stone Brick Stairs"),
+    (5052, 5131, Shape::Stairs, "Nether Brick Stairs"),
+    (5136, 5136, Shape::Height(12), "Enchanting Table"),
+    (5174, 5253, Shape::Stairs, "Sandstone Stairs"),
+    (5255, 5262, Shape::Chest, "Ender Chest"),
+    (5408, 5647, Shape::Stairs, "Spruce, Birch and Jungle Stairs"),
+    (6626, 6649, Shape::Chest, "Trapped Chest"),
+    (6732, 6741, Shape::Hopper, "Hopper"),
+    (6747, 6826, Shape::Stairs, "Quartz Stairs"),
+    (7379, 7538, Shape::Stairs, "Acacia and Dark Oak Stairs"),
+    (7541, 7604, Shape::Trapdoor, "Iron Trapdoor"),
+    (7608, 7847, Shape::Stairs, "Prismarine Stairs"),
+    (7848, 7865, Shape::Slab, "Prismarine Slabs"),
+    (7870, 7885, Shape::Height(1), "Carpets"),
+    (8224, 8303, Shape::Stairs, "Red Sandstone Stairs"),
+    (8304, 8417, Shape::Slab, "Wooden and Stone Slabs"),
+    (9142, 9221, Shape::Stairs, "Purpur Stairs"),
+    (9227, 9227, Shape::Height(15), "Grass Path"),
+    (9673, 10792, Shape::Stairs, "Granite to Diorite Stairs"),
+    (10793, 10870, Shape::Slab, "Granite to Diorite Slabs"),
+    (15055, 15066, Shape::Slab, "Crimson and Warped Slabs"),
+    (15135, 15262, Shape::Trapdoor, "Nether Wood Trapdoors"),
+    (15327, 15486, Shape::Stairs, "Crimson and Warped Stairs"),
+    (15848, 15927, Shape::Stairs, "Blackstone Stairs"),
+    (16252, 16257, Shape::Slab, "Blackstone Slab"),
+    (16262, 16267, Shape::Slab, "Polished Blackstone Brick Slab"),
+    (16268, 16347, Shape::Stairs, "Blackstone Brick Stairs"),
+    (16673, 16752, Shape::Stairs, "Polished Blackstone Stairs"),
+    (16753, 16758, Shape::Slab, "Polished Blackstone Slab"),
+];
+
+// a box inside a block, in sixteenths
+type Box16 = ([u8; 3], [u8; 3]);
+
+const FULL: Box16 = ([0, 0, 0], [16, 16, 16]);
+
+// the boxes that entities collide with, as (min, max) corners relative to the block
+pub fn collision_boxes(state: u32) -> Vec<([f64; 3], [f64; 3])> {
+    if is_passable(state) {
+        return Vec::new();
+    }
+
+    let shape = SHAPES
+        .iter()
+        .find(|&&(first, last, _, _)| state >= first && state <= last);
+    let boxes = match shape {
+        None => vec![FULL],
+        Some(&(first, _, shape, _)) => {
+            let i = state - first;
+            match shape {
+                Shape::Height(height) => vec![([0, 0, 0], [16, height, 16])],
+                Shape::Chest => vec![([1, 0, 1], [15, 14, 15])],
+                // the rim, the bottom of the bowl and the middle part, the spout is left out
+                Shape::Hopper => vec![
+                    ([0, 10, 0], [16, 11, 16]),
+                    ([0, 11, 0], [2, 16, 16]),
+                    ([14, 11, 0], [16, 16, 16]),
+                    ([2, 11, 0], [14, 16, 2]),
+                    ([2, 11, 14], [14, 16, 16]),
+                    ([4, 4, 4], [12, 10, 12]),
+                ],
+                // type (top, bottom, double) then waterlogged
+                Shape::Slab => match i % 6 / 2 {
+                    0 => vec![([0, 8, 0], [16, 16, 16])],
+                    1 => vec![([0, 0, 0], [16, 8, 16])],
+                    _ => vec![FULL],
+                },
+                // from 1 to 8 layers, a single layer can be walked through
+                Shape::SnowLayers if i == 0 => Vec::new(),
+                Shape::SnowLayers => vec![([0, 0, 0], [16, 2 * i as u8, 16])],
+                Shape::Stairs => stairs(i % 80),
+                Shape::Trapdoor => vec![trapdoor(i % 64)],
+            }
+        }
+    };
+
+    let to_blocks = |corner: [u8; 3]| {
+        [
+            f64::from(corner[0]) / 16.0,
+            f64::from(corner[1]) / 16.0,
+            f64::from(corner[2]) / 16.0,
+        ]
+    };
+    boxes
+        .into_iter()
+        .map(|(min, max)| (to_blocks(min), to_blocks(max)))
+        .collect()
+}
+
+// the state is facing (north, south, west, east), half (top, bottom),
+// shape (straight, inner left, inner right, outer left, outer right) then waterlogged
+fn stairs(i: u32) -> Vec<Box16> {
+    let facing = i / 20;
+    let half = i / 10 % 2;
+    let shape = i / 2 % 5;
+
+    // the quarters of the step as (x, z) for stairs facing north, the step is on their back
+    let quarters: &[(u8, u8)] = match shape {
+        0 => &[(0, 0), (1, 0)],
+        1 => &[(0, 0), (1, 0), (0, 1)],
+        2 => &[(0, 0), (1, 0), (1, 1)],
+        3 => &[(0, 0)],
+        _ => &[(1, 0)],
+    };
+    // clockwise quarter turns from north
+    let turns = [0, 2, 3, 1][facing as usize];
+
+    // upside down stairs have their step at the bottom
+    let (slab, step) = match half {
+        0 => ((8, 16), (0, 8)),
+        _ => ((0, 8), (8, 16)),
+    };
+    let mut boxes = vec![([0, slab.0, 0], [16, slab.1, 16])];
+    for &(mut x, mut z) in quarters {
+        for _ in 0..turns {
+            let turned = (1 - z, x);
+            x = turned.0;
+            z = turned.1;
+        }
+        boxes.push(([x * 8, step.0, z * 8], [x * 8 + 8, step.1, z * 8 + 8]));
+    }
+    boxes
+}
+
+// the state is facing (north, south, west, east), half (top, bottom), open, powered
+// then waterlogged
+fn trapdoor(i: u32) -> Box16 {
+    let (facing, half, open) = (i / 16, i / 8 % 2, i / 4 % 2);
+    match (open, half, facing) {
+        // closed ones lie in the half they were placed in
+        (1, 0, _) => ([0, 13, 0], [16, 16, 16]),
+        (1, _, _) => ([0, 0, 0], [16, 3, 16]),
+        // open ones stand against the side opposite to their facing
+        (_, _, 0) => ([0, 0, 13], [16, 16, 16]),
+        (_, _, 1) => ([0, 0, 0], [16, 16, 3]),
+        (_, _, 2) => ([13, 0, 0], [16, 16, 16]),
+        _ => ([0, 0, 0], [3, 16, 16]),
+    }
+}
+
+pub fn is_water(state: u32) -> bool {
+    (34..=49).contains(&state) || (9671..=9672).contains(&state)
+}
+
+pub fn is_lava(state: u32) -> bool {
+    (50..=65).contains(&state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges_are_in_order() {
+        for window in PASSABLE.windows(2) {
+            assert!(window[0].0 <= window[0].1, "{}", window[0].2);
+            assert!(window[0].1 < window[1].0, "{}", window[1].2);
+        }
+        for window in SHAPES.windows(2) {
+            assert!(window[0].0 <= window[0].1, "{}", window[0].3);
+            assert!(window[0].1 < window[1].0, "{}", window[1].3);
+        }
+    }
+
+    #[test]
+    fn passable() {
+        assert!(is_passable(0)); // air
+        assert!(!is_passable(1)); // stone
+        assert!(is_passable(34) && is_passable(65)); // water and lava
+        assert!(is_passable(1341)); // cobweb
+        assert!(!is_passable(3573)); // past the signs
+        assert!(is_passable(9670) && is_passable(9672)); // cave air and bubble columns
+        assert!(!is_passable(u32::MAX));
+    }
+
+    #[test]
+    fn liquids() {
+        assert!(is_water(34) && is_water(49) && is_water(9671));
+        assert!(!is_water(50) && !is_water(33));
+        assert!(is_lava(50) && is_lava(65));
+        assert!(!is_lava(49) && !is_lava(66));
+    }
+
+    fn tops(state: u32) -> Vec<f64> {
+        collision_boxes(state)
+            .iter()
+            .map(|(_, max)| max[1])
+            .collect()
+    }
+
+    #[test]
+    fn full_and_empty_blocks() {
+        assert_eq!(collision_boxes(1), vec![([0.0; 3], [1.0; 3])]); // stone
+        assert!(collision_boxes(0).is_empty()); // air
+        assert!(collision_boxes(34).is_empty()); // water
+    }
+
+    #[test]
+    fn partial_blocks() {
+        assert_eq!(tops(3365), vec![0.9375]); // farmland
+        assert_eq!(tops(9227), vec![0.9375]); // grass path
+        assert_eq!(tops(4000), vec![0.875]); // soul sand
+        assert_eq!(tops(7870), vec![0.0625]); // white carpet
+        assert_eq!(tops(2034), vec![0.875]); // chest
+        assert!(tops(3921).is_empty()); // a single snow layer
+        assert_eq!(tops(3922), vec![0.125]);
+        assert_eq!(tops(3928), vec![0.875]); // 8 layers
+    }
+
+    #[test]
+    fn slabs() {
+        // stone slab: top, bottom and double, each waterlogged or not
+        let stone = 8340;
+        assert_eq!(collision_boxes(stone), vec![([0.0, 0.5, 0.0], [1.0; 3])]);
+        assert_eq!(
+            collision_boxes(stone + 3),
+            vec![([0.0; 3], [1.0, 0.5, 1.0])]
+        );
+        assert_eq!(collision_boxes(stone + 5), vec![([0.0; 3], [1.0; 3])]);
+        // the last polished blackstone slab is a double one too
+        assert_eq!(collision_boxes(16758), vec![([0.0; 3], [1.0; 3])]);
+    }
+
+    #[test]
+    fn stairs() {
+        let oak = 1954;
+        let state = |facing: u32, top: u32, shape: u32| oak + ((facing * 2 + top) * 5 + shape) * 2;
+        let bottom_slab = ([0.0; 3], [1.0, 0.5, 1.0]);
+
+        // straight, facing north: the step is on the north half
+        assert_eq!(
+            collision_boxes(state(0, 1, 0)),
+            vec![
+                bottom_slab,
+                ([0.0, 0.5, 0.0], [0.5, 1.0, 0.5]),
+                ([0.5, 0.5, 0.0], [1.0, 1.0, 0.5]),
+            ]
+        );
+        // straight, facing east: the step is on the east half
+        assert_eq!(
+            collision_boxes(state(3, 1, 0)),
+            vec![
+                bottom_slab,
+                ([0.5, 0.5, 0.0], [1.0, 1.0, 0.5]),
+                ([0.5, 0.5, 0.5], [1.0; 3]),
+            ]
+        );
+        // outer left, facing south: only the south east corner
+        assert_eq!(
+            collision_boxes(state(1, 1, 3)),
+            vec![bottom_slab, ([0.5; 3], [1.0; 3])]
+        );
+        // upside down, the step is at the bottom
+        let boxes = collision_boxes(state(2, 0, 0));
+        assert_eq!(boxes[0], ([0.0, 0.5, 0.0], [1.0; 3]));
+        assert!(boxes[1..]
+            .iter()
+            .all(|(min, max)| min[1] == 0.0 && max[1] == 0.5));
+        assert_eq!(boxes.len(), 3);
+    }
+
+    #[test]
+    fn trapdoors() {
+        let oak = 4111;
+        // facing north, top half, closed
+        assert_eq!(
+            collision_boxes(oak + 4),
+            vec![([0.0, 0.8125, 0.0], [1.0; 3])]
+        );
+        // facing north, bottom half, closed
+        assert_eq!(
+            collision_boxes(oak + 12),
+            vec![([0.0; 3], [1.0, 0.1875, 1.0])]
+        );
+        // facing north, open
+        assert_eq!(collision_boxes(oak), vec![([0.0, 0.0, 0.8125], [1.0; 3])]);
+    }
+}
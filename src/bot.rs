@@ -1,74 +1,197 @@
+use crate::anti_afk::{self, Action};
+use crate::behavior::{self, Behavior, Context, Hook};
+use crate::behaviors;
+use crate::chat;
 use crate::client::Client;
 use crate::datatypes::*;
-use crate::mobs::MOBS;
+use crate::export;
+use crate::handle::{Event, Request};
+use crate::metrics::Metrics;
+use crate::mobs::{self, MOBS};
+use crate::objects::{self, Kind};
 use crate::packets::*;
+use crate::pathfinding;
+use crate::physics;
+use crate::registries;
+use crate::remote::{self, Command};
+use crate::schedule::{Schedule, ScheduledMessage};
+use crate::scripts::Scripts;
+use crate::world::{self, Chunk, World};
 use crate::Settings;
-use crate::{clone_all, clone_mut};
-use fltk::Color;
-use std::collections::HashMap;
+use crate::clone_all;
+use chrono::Local;
+use log::Level;
+use std::collections::{BTreeMap, HashMap};
 use std::io::{Error, ErrorKind};
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::io;
 use tokio::net::TcpStream;
 use tokio::sync::mpsc;
+use tokio::sync::oneshot::{self, error::TryRecvError};
 use tokio::sync::Mutex;
 
+// what the bot knows about itself and the world around it
 #[derive(Debug)]
-struct State {
-    shutdown: bool,
-    my_entity_id: Option<VarInt>,
-    my_pos: (f64, f64, f64),
-    mobs: HashMap<VarInt, Mob>,
-    hotbar: [Slot; 9],
-    sneaking: bool,
-    held_item: u8,
-    food: f32,
-    initial_statistics: Vec<(VarInt, VarInt, VarInt)>,
-    statistics: Vec<(VarInt, VarInt, VarInt)>,
+pub struct State {
+    pub(crate) shutdown: bool,
+    pub(crate) disconnect: Option<Disconnect>, // why the bot is disconnecting, if it's its own decision
+    pub my_entity_id: Option<VarInt>,
+    pub my_pos: (f64, f64, f64),
+    pub my_rotation: (f32, f32), // yaw, pitch
+    pub velocity: (f64, f64, f64),
+    pub on_ground: bool,
+    pub position_received: bool, // the server has told us where we are
+    pub mobs: HashMap<VarInt, Mob>,
+    pub players: HashMap<VarInt, Player>,         // other players that are in view
+    pub(crate) player_names: HashMap<u128, String>, // UUID -> name, from the tab list
+    pub(crate) objects: HashMap<VarInt, Object>,
+    pub(crate) dropped_items: u32,      // items that appeared near the bot
+    pub(crate) dropped_experience: i64, // experience in orbs that appeared near the bot
+    pub joined_at: Instant,
+    pub(crate) picked_up: BTreeMap<i64, i64>, // item id -> how many were picked up
+    pub experience: Option<(i64, i64)>, // level, total experience points
+    pub(crate) levels_gained: i64,
+    pub(crate) experience_gained: i64,
+    pub hotbar: [Slot; 9],
+    pub sneaking: bool,
+    pub held_item: u8,
+    pub health: f32,
+    pub food: f32,
+    pub saturation: f32,
+    pub eat_now: bool, // eat even if not hungry, requested remotely
+    pub(crate) initial_statistics: Vec<(VarInt, VarInt, VarInt)>,
+    pub(crate) statistics: Vec<(VarInt, VarInt, VarInt)>,
+    pub(crate) login_sent: bool,
+    pub death_pos: Option<(f64, f64, f64)>,
+    pub(crate) respawned: bool, // waiting for the position after respawning
+    pub anchor: Option<(f64, f64, f64)>, // the AFK spot to walk back to when knocked away
+    pub(crate) world: World,
+}
+
+impl State {
+    pub(crate) fn new() -> Self {
+        Self {
+            shutdown: false,
+            disconnect: None,
+            my_entity_id: None,
+            my_pos: (0.0, 0.0, 0.0),
+            my_rotation: (0.0, 0.0),
+            velocity: (0.0, 0.0, 0.0),
+            on_ground: false,
+            position_received: false,
+            mobs: HashMap::new(),
+            players: HashMap::new(),
+            player_names: HashMap::new(),
+            objects: HashMap::new(),
+            dropped_items: 0,
+            dropped_experience: 0,
+            joined_at: Instant::now(),
+            picked_up: BTreeMap::new(),
+            experience: None,
+            levels_gained: 0,
+            experience_gained: 0,
+            hotbar: [Slot::NotPresent; 9],
+            sneaking: false,
+            held_item: 0,
+            health: 20.0,
+            food: 20.0,
+            saturation: 5.0,
+            eat_now: false,
+            initial_statistics: Vec::new(),
+            statistics: Vec::new(),
+            login_sent: false,
+            death_pos: None,
+            respawned: false,
+            anchor: None,
+            world: World::default(),
+        }
+    }
+}
+
+// why the bot stopped, so that a planned disconnect can be told apart from a problem
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Disconnect {
+    Requested, // by the user or an owner with /msg
+    LowHealth,
+    Error, // lost the connection or got kicked
 }
 
 #[derive(Debug)]
-struct Mob {
-    entity_id: VarInt,
-    entity_type: VarInt,
+pub struct Mob {
+    pub entity_id: VarInt,
+    pub entity_type: VarInt,
+    pub pos: (f64, f64, f64),
+}
+
+#[derive(Debug)]
+pub struct Player {
+    pub entity_id: VarInt,
+    pub name: String,
+    pub pos: (f64, f64, f64),
+    pub(crate) nearby: bool, // already reported as having come close
+}
+
+#[derive(Debug)]
+pub(crate) struct Object {
+    kind: Kind,
     pos: (f64, f64, f64),
+    velocity: (f64, f64, f64), // blocks per tick
+    item: Option<(i64, i8)>,   // item id and count for dropped items, from the metadata
 }
 
-pub async fn run<W, C, S>(
+// how the bot talks to its handle
+pub(crate) struct Channels {
+    pub events: mpsc::UnboundedSender<Event>,
+    pub requests: mpsc::Receiver<Request>,
+    pub shutdown_receiver: mpsc::Receiver<()>,
+    pub shutdown_sender: mpsc::Sender<()>,
+}
+
+pub async fn run(
     ip: String,
     username: String,
     settings: Arc<Mutex<Settings>>,
-    write_to_log: W,
-    change_status: C,
-    mut shutdown_receiver: mpsc::Receiver<()>,
-    shutdown_sender: mpsc::Sender<()>,
-    update_statistics: S,
-) -> io::Result<()>
-where
-    W: FnMut(String) + Clone + Send + 'static,
-    C: FnMut(String, Color) + Clone + Send + 'static,
-    S: FnMut(String) + Clone + Send + 'static,
-{
+    state: Arc<Mutex<State>>,
+    metrics: Arc<Metrics>,
+    channels: Channels,
+    custom_behaviors: Vec<Box<dyn Behavior>>,
+) -> io::Result<Disconnect> {
+    let Channels {
+        events,
+        mut requests,
+        mut shutdown_receiver,
+        shutdown_sender,
+    } = channels;
+
+    // everything that's logged is an event too
+    let log_with_level = {
+        clone_all![events];
+        move |level: Level, message: String| {
+            // fails only if nobody is listening to the events anymore
+            let _ = events.send(Event::Log(level, message));
+        }
+    };
+    let write_to_log = {
+        clone_all![log_with_level];
+        move |message: String| log_with_level(Level::Info, message)
+    };
+
     let stream = TcpStream::connect(&ip).await?;
 
     let mut client = Client {
         stream,
         status: 0,
         compression: -1,
+        metrics,
     };
 
-    let state = State {
-        shutdown: false,
-        my_entity_id: None,
-        my_pos: (0.0, 0.0, 0.0),
-        mobs: HashMap::new(),
-        hotbar: [Slot::NotPresent; 9],
-        sneaking: false,
-        held_item: settings.lock().await.weapon,
-        food: 20.0,
-        initial_statistics: Vec::new(),
-        statistics: Vec::new(),
-    };
+    // the state is made before connecting, so that the handle can read it, but the clock starts now
+    {
+        let mut state_lock = state.lock().await;
+        state_lock.held_item = settings.lock().await.weapon;
+        state_lock.joined_at = Instant::now();
+    }
 
     client
         .send(ServerBound::Handshake(
@@ -84,10 +207,9 @@ where
         .await?;
 
     let client = Arc::new(Mutex::new(client));
-    let state = Arc::new(Mutex::new(state));
 
-    async fn check<W: FnMut(String) + Clone + Send + 'static>(
-        mut write_to_log: W,
+    async fn check<L: FnMut(Level, String) + Clone + Send + 'static>(
+        mut log_with_level: L,
         client: Arc<Mutex<Client>>,
         state: Arc<Mutex<State>>,
         shutdown_sender: mpsc::Sender<()>,
@@ -96,93 +218,74 @@ where
         if let Err(e) = res {
             if state.lock().await.shutdown {
                 if e.kind() != ErrorKind::BrokenPipe && e.kind() != ErrorKind::UnexpectedEof {
-                    write_to_log(format!("Error: {}", e));
+                    log_with_level(Level::Error, format!("Error: {}", e));
                 }
             } else {
-                write_to_log(format!("Error: {}", e));
-                state.lock().await.shutdown = true;
-                client
+                log_with_level(Level::Error, format!("Error: {}", e));
+                let mut state_lock = state.lock().await;
+                state_lock.shutdown = true;
+                state_lock.disconnect.get_or_insert(Disconnect::Error);
+                drop(state_lock);
+                // fails if the server has already closed the connection
+                let _ = client
                     .lock()
                     .await
                     .stream
-                    .shutdown(std::net::Shutdown::Both)
-                    .unwrap();
+                    .shutdown(std::net::Shutdown::Both);
             }
-            shutdown_sender.send(()).await.unwrap();
+            // fails if another task's error already ended the bot, which is fine
+            let _ = shutdown_sender.send(()).await;
         }
     }
 
-    // spawn a task to start/stop sneaking depending on the settings in real time
-    // and change held item
-    let task1 = tokio::spawn({
-        clone_all![client, state, settings, write_to_log, shutdown_sender];
-        async move {
-            check(
-                write_to_log,
-                client.clone(),
-                state.clone(),
-                shutdown_sender,
-                async move {
-                    loop {
-                        tokio::time::sleep(std::time::Duration::from_secs_f32(0.1)).await;
-                        let mut state_lock = state.lock().await;
-                        let settings_lock = settings.lock().await;
-                        // change hotbar item if needed
-                        if state_lock.held_item != settings_lock.weapon {
-                            client
-                                .lock()
-                                .await
-                                .send(ServerBound::HeldItemChange(settings_lock.weapon as i16))
-                                .await?;
-                            state_lock.held_item = settings_lock.weapon;
-                        }
+    // the built-in behaviors that are turned on, the scripts, then the ones that were given
+    let mut behaviors = Vec::new();
+    for name in settings.lock().await.behaviors.clone() {
+        match behaviors::built_in(&name) {
+            Some(behavior) => behaviors.push(behavior),
+            None => log_with_level(Level::Warn, format!("There's no behavior {:?}", name)),
+        }
+    }
+    let scripts_folder = settings.lock().await.scripts_folder.clone();
+    if !scripts_folder.is_empty() {
+        behaviors.push(Box::new(Scripts::new(scripts_folder.into())));
+    }
+    behaviors.extend(custom_behaviors);
 
-                        // sneaking things
-                        if let Some(my_entity_id) = state_lock.my_entity_id {
-                            let settings_sneaking = settings_lock.sneak;
-                            let current_sneaking = state_lock.sneaking;
-                            if settings_sneaking == current_sneaking {
-                                continue;
-                            }
-                            if settings_sneaking {
-                                // start sneaking
-                                client
-                                    .lock()
-                                    .await
-                                    .send(ServerBound::EntityAction(
-                                        VarInt(my_entity_id.0),
-                                        VarInt(0),
-                                        VarInt(0),
-                                    ))
-                                    .await?;
-                            } else {
-                                // stop sneaking
-                                client
-                                    .lock()
-                                    .await
-                                    .send(ServerBound::EntityAction(
-                                        VarInt(my_entity_id.0),
-                                        VarInt(1),
-                                        VarInt(1),
-                                    ))
-                                    .await?;
-                            }
-                            state_lock.sneaking = settings_sneaking;
-                        }
-                    }
+    // spawn a task for each behavior, the hooks are sent to it through a channel
+    let mut hooks = Vec::new();
+    let mut behavior_tasks = Vec::new();
+    for mut behavior in behaviors {
+        let (hook_sender, mut hook_receiver) = mpsc::unbounded_channel();
+        hooks.push(hook_sender);
+        behavior_tasks.push(tokio::spawn({
+            clone_all![client, state, settings, events, log_with_level, shutdown_sender, ip];
+            async move {
+                let mut context = Context {
+                    state: state.clone(),
+                    client: client.clone(),
+                    settings,
+                    events,
+                    address: ip,
+                };
+                let res = behavior::run(&mut behavior, &mut context, &mut hook_receiver).await;
+                let failed = res.is_err();
+                check(log_with_level, client, state, shutdown_sender, res).await;
+                if failed {
+                    behavior::wait_for_disconnect(&mut hook_receiver).await;
                 }
-                .await,
-            )
-            .await;
-        }
-    });
+                behavior.on_disconnect(&mut context).await;
+            }
+        }));
+    }
+    let hooks = Arc::new(hooks);
 
     // spawn a task for querying the statistics
     let task2 = tokio::spawn({
-        clone_all![client, state, write_to_log, shutdown_sender];
+        clone_all![client, state, log_with_level, shutdown_sender];
         async move {
             check(
-                write_to_log,
+                log_with_level,
                 client.clone(),
                 state.clone(),
                 shutdown_sender,
@@ -203,191 +306,6 @@ where
         }
     });
 
-    // also spawn a task for eating when needed
-    let task3 = tokio::spawn({
-        clone_mut![write_to_log];
-        clone_all![client, state, settings, shutdown_sender];
-        async move {
-            // the compiler desires this to be put into a variable for some reason
-            let write_to_log_clone = write_to_log.clone();
-            check(
-                write_to_log_clone,
-                client.clone(),
-                state.clone(),
-                shutdown_sender,
-                async move {
-                    loop {
-                        tokio::time::sleep(std::time::Duration::from_secs_f32(1.0)).await;
-                        let state_lock = state.lock().await;
-                        let settings_lock = settings.lock().await;
-
-                        if state_lock.food < settings_lock.eat_at {
-                            // gotta eat something
-                            // find food in hotbar
-                            for slot_id in 0..9 {
-                                if let Slot::Present(id, _number) = &state_lock.hotbar[slot_id] {
-                                    if settings_lock.eat_food.contains(&id.0) {
-                                        write_to_log(format!("Eating."));
-                                        // its eatable and allowed to eat
-                                        // eat it
-                                        client
-                                            .lock()
-                                            .await
-                                            .send(ServerBound::HeldItemChange(slot_id as i16))
-                                            .await?;
-                                        client
-                                            .lock()
-                                            .await
-                                            .send(ServerBound::UseItem(VarInt(0)))
-                                            .await?;
-                                        // wait 1.63 s and then finish eating
-                                        tokio::time::sleep(std::time::Duration::from_secs_f32(
-                                            1.61,
-                                        ))
-                                        .await;
-                                        client
-                                            .lock()
-                                            .await
-                                            .send(ServerBound::PlayerDigging(VarInt(5), 0, 0))
-                                            .await?;
-                                        client
-                                            .lock()
-                                            .await
-                                            .send(ServerBound::HeldItemChange(
-                                                settings_lock.weapon as i16,
-                                            ))
-                                            .await?;
-                                        // stop searching for food this iteration
-                                        break;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-                .await,
-            )
-            .await;
-        }
-    });
-
-    // spawn a task for attacking nearby mobs every once in a while
-    let task4 = tokio::spawn({
-        clone_all![client, state, settings, write_to_log, shutdown_sender];
-        async move {
-            check(
-                write_to_log,
-                client.clone(),
-                state.clone(),
-                shutdown_sender,
-                async move {
-                    let mut i = 0;
-                    loop {
-                        let attack_speed = settings.lock().await.attack_speed;
-                        let to_sleep = if attack_speed == 0.0 {
-                            0.5
-                        } else {
-                            1.0 / attack_speed
-                        };
-                        tokio::time::sleep(std::time::Duration::from_secs_f32(to_sleep)).await;
-                        if attack_speed == 0.0 {
-                            // skip the attack
-                            continue;
-                        }
-                        // calculate the nearest mob
-                        let state_lock = state.lock().await;
-                        if state_lock.mobs.len() > 0 {
-                            let sq_dist = |mob: &Mob| {
-                                let my_pos = state_lock.my_pos;
-
-                                (my_pos.0 - mob.pos.0).powi(2)
-                                    + (my_pos.1 - mob.pos.1).powi(2)
-                                    + (my_pos.2 - mob.pos.2).powi(2)
-                            };
-
-                            let mut nearest_mob_id = None;
-                            let mut nearest_mob_squared_distance = None;
-                            let settings_lock = settings.lock().await;
-                            for (id, mob) in state_lock.mobs.iter() {
-                                if settings_lock.attack_mobs.contains(&mob.entity_type.0) {
-                                    let dist = sq_dist(mob);
-                                    if let Some(temp_nearest_mob_squared_distance) =
-                                        nearest_mob_squared_distance
-                                    {
-                                        if dist < temp_nearest_mob_squared_distance {
-                                            nearest_mob_id = Some(id.clone());
-                                            nearest_mob_squared_distance = Some(dist);
-                                        }
-                                    } else {
-                                        nearest_mob_id = Some(id.clone());
-                                        nearest_mob_squared_distance = Some(dist);
-                                    }
-                                }
-                            }
-                            drop(settings_lock);
-                            // if a mob was found, attack it
-                            if let (Some(nearest_mob_id), Some(nearest_mob_squared_distance)) =
-                                (nearest_mob_id, nearest_mob_squared_distance)
-                            {
-                                if nearest_mob_squared_distance >= 16.0 {
-                                    continue;
-                                }
-
-                                // change the player rotation to look at the mob
-                                // this is not neccessary but helps when debugging
-                                // because you can see what mob the bot is trying to attack
-                                //
-                                // calculate pitch and yaw
-                                let mob = &state_lock.mobs[&nearest_mob_id];
-                                let dx = mob.pos.0 - state_lock.my_pos.0;
-                                let dy = mob.pos.1 - state_lock.my_pos.1;
-                                let dz = mob.pos.2 - state_lock.my_pos.2;
-                                let r = (dx * dx + dy * dy + dz * dz).sqrt();
-                                let mut yaw = -dx.atan2(dz) / std::f64::consts::PI * 180.0;
-                                if yaw < 0.0 {
-                                    yaw = 360.0 + yaw;
-                                }
-                                let pitch = -(dy / r).asin() / std::f64::consts::PI * 180.0;
-                                client
-                                    .lock()
-                                    .await
-                                    .send(ServerBound::PlayerPositionAndRotation(
-                                        state_lock.my_pos.0,
-                                        state_lock.my_pos.1 - 1.62,
-                                        state_lock.my_pos.2,
-                                        yaw as f32,
-                                        pitch as f32,
-                                        true,
-                                    ))
-                                    .await?;
-
-                                // attack the mob
-                                client
-                                    .lock()
-                                    .await
-                                    .send(ServerBound::InteractEntity(
-                                        nearest_mob_id,
-                                        VarInt(1),
-                                        settings.lock().await.sneak,
-                                    ))
-                                    .await?;
-                                // also animation
-                                client
-                                    .lock()
-                                    .await
-                                    .send(ServerBound::Animation(VarInt(i)))
-                                    .await?;
-                                i = (i + 1) % 2;
-                            }
-                        }
-                    }
-                }
-                .await,
-            )
-            .await;
-        }
-    });
-
     // spawn a task for processing incoming packets
     let task5 = tokio::spawn({
         clone_all![
@@ -395,30 +313,38 @@ where
             state,
             settings,
             write_to_log,
+            log_with_level,
             shutdown_sender,
-            update_statistics
+            events,
+            ip,
+            hooks
         ];
         async move {
             // the compiler desires this to be put into a variable for some reason
-            let write_to_log_clone = write_to_log.clone();
+            let log_with_level_clone = log_with_level.clone();
             check(
-                write_to_log_clone,
+                log_with_level_clone,
                 client.clone(),
                 state.clone(),
                 shutdown_sender.clone(),
                 async move {
                     loop {
                         let packet = client.lock().await.receive().await?;
+                        if !hooks.is_empty() {
+                            let shared = Arc::new(packet.clone());
+                            for hook in hooks.iter() {
+                                let _ = hook.send(Hook::Packet(shared.clone()));
+                            }
+                        }
 
-                        // spawn a task for proccessing this packet
-                        tokio::spawn({
-                            clone_mut![change_status, write_to_log, update_statistics];
-                            clone_all![client, state, settings, shutdown_sender];
+                        let ordered = is_ordered(&packet);
+                        let process = {
+                            clone_all![client, state, settings, write_to_log, log_with_level, shutdown_sender, events, ip, hooks];
                             // pls rustc
-                            let write_to_log_clone = write_to_log.clone();
+                            let log_with_level_clone = log_with_level.clone();
                             async move {
                                 check(
-                                    write_to_log_clone,
+                                    log_with_level_clone,
                                     client.clone(),
                                     state.clone(),
                                     shutdown_sender.clone(),
@@ -442,19 +368,25 @@ where
                                                         settings.lock().await.weapon as i16,
                                                     ))
                                                     .await?;
+                                                for hook in hooks.iter() {
+                                                    let _ = hook.send(Hook::Join);
+                                                }
                                             }
-                                            ClientBound::UpdateHealth(health, food, _) => {
-                                                change_status(
-                                                    format!(
-                                                        "health: {}/20   food: {}/20",
-                                                        health, food.0
-                                                    ),
-                                                    Color::DarkGreen,
-                                                );
-                                                state.lock().await.food = food.0 as f32;
-                                                let min_health = settings.lock().await.min_health;
-                                                if (health as f32) < min_health {
-                                                    write_to_log(format!("Health ({}) below {}, disconnecting to avoid death.", health, min_health));
+                                            ClientBound::UpdateHealth(health, food, saturation) => {
+                                                let mut state_lock = state.lock().await;
+                                                state_lock.health = health;
+                                                state_lock.food = food.0 as f32;
+                                                state_lock.saturation = saturation;
+                                                drop(state_lock);
+                                                let _ = events.send(Event::HealthChanged { health, food: food.0 as f32 });
+                                                let settings_lock = settings.lock().await;
+                                                let min_health = settings_lock.min_health;
+                                                // no point in disconnecting if already dead and going to respawn
+                                                let respawning = health <= 0.0 && settings_lock.auto_respawn;
+                                                drop(settings_lock);
+                                                if health < min_health && !respawning {
+                                                    log_with_level(Level::Warn, format!("Health ({}) below {}, disconnecting to avoid death.", health, min_health));
+                                                    state.lock().await.disconnect.get_or_insert(Disconnect::LowHealth);
 
                                                     shutdown_sender.send(()).await.unwrap();
                                                 }
@@ -463,18 +395,101 @@ where
                                                 x,
                                                 y,
                                                 z,
-                                                _yaw,
-                                                _pitch,
+                                                yaw,
+                                                pitch,
                                                 _,
                                                 id,
                                             ) => {
-                                                state.lock().await.my_pos = (x, y, z);
+                                                let mut state_lock = state.lock().await;
+                                                state_lock.my_pos = (x, y, z);
+                                                state_lock.my_rotation = (yaw, pitch);
+                                                state_lock.velocity = (0.0, 0.0, 0.0);
+                                                state_lock.position_received = true;
+                                                // the first position is the AFK spot, and so is the destination of any teleport too far to walk back from
+                                                let too_far = match state_lock.anchor {
+                                                    Some(anchor) => physics::horizontal_distance(anchor, (x, y, z)) > physics::MAX_WALK_DISTANCE || (anchor.1 - y).abs() > physics::MAX_WALK_DISTANCE,
+                                                    None => true,
+                                                };
+                                                if too_far {
+                                                    if state_lock.anchor.is_some() && !state_lock.respawned {
+                                                        write_to_log(format!("Teleported away, the new AFK spot is {:.0} {:.0} {:.0}.", x, y, z));
+                                                    }
+                                                    state_lock.anchor = Some((x, y, z));
+                                                }
+                                                if state_lock.respawned {
+                                                    state_lock.respawned = false;
+                                                    if let Some(death_pos) = state_lock.death_pos.take() {
+                                                        let distance = ((x - death_pos.0).powi(2) + (y - death_pos.1).powi(2) + (z - death_pos.2).powi(2)).sqrt();
+                                                        if too_far {
+                                                            write_to_log(format!("Respawned {:.0} blocks away from the death spot, can't walk back.", distance));
+                                                        } else if distance > 1.0 && settings.lock().await.return_to_spot {
+                                                            write_to_log(format!("Respawned {:.0} blocks away from the death spot, walking back.", distance));
+                                                        }
+                                                    }
+                                                }
+                                                drop(state_lock);
                                                 client
                                                     .lock()
                                                     .await
                                                     .send(ServerBound::TeleportConfirm(id))
                                                     .await?;
                                             }
+                                            ClientBound::SpawnEntity(entity_id, _, entity_type, x, y, z, _, _, _, velocity_x, velocity_y, velocity_z) => {
+                                                let mut state_lock = state.lock().await;
+                                                let kind = objects::kind(entity_type.0);
+                                                let velocity = (velocity_x as f64 / 8000.0, velocity_y as f64 / 8000.0, velocity_z as f64 / 8000.0);
+                                                let my_pos = state_lock.my_pos;
+                                                let distance = ((x - my_pos.0).powi(2) + (y - my_pos.1).powi(2) + (z - my_pos.2).powi(2)).sqrt();
+                                                match kind {
+                                                    Kind::Item if distance < 16.0 => state_lock.dropped_items += 1,
+                                                    Kind::Tnt if distance < 8.0 => log_with_level(Level::Warn, format!("Lit TNT {:.0} blocks away!", distance)),
+                                                    Kind::Arrow | Kind::Fireball if dodge(&mut state_lock, (x, y, z), velocity) => {
+                                                        write_to_log("Dodging a projectile.".to_string());
+                                                    }
+                                                    _ => {}
+                                                }
+                                                state_lock.objects.insert(entity_id, Object { kind, pos: (x, y, z), velocity, item: None });
+                                            }
+                                            ClientBound::SpawnExperienceOrb(entity_id, x, y, z, amount) => {
+                                                let mut state_lock = state.lock().await;
+                                                let my_pos = state_lock.my_pos;
+                                                if ((x - my_pos.0).powi(2) + (y - my_pos.1).powi(2) + (z - my_pos.2).powi(2)).sqrt() < 16.0 {
+                                                    state_lock.dropped_experience += amount as i64;
+                                                }
+                                                state_lock.objects.insert(entity_id, Object { kind: Kind::ExperienceOrb(amount), pos: (x, y, z), velocity: (0.0, 0.0, 0.0), item: None });
+                                            }
+                                            ClientBound::EntityMetadata(entity_id, metadata) => {
+                                                // only dropped items are interesting
+                                                let is_item = matches!(state.lock().await.objects.get(&entity_id), Some(Object { kind: Kind::Item, .. }));
+                                                if is_item {
+                                                    // not worth disconnecting over
+                                                    if let Ok(Some(item)) = objects::item_from_metadata(metadata).await {
+                                                        if let Some(object) = state.lock().await.objects.get_mut(&entity_id) {
+                                                            object.item = Some(item);
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                            ClientBound::CollectItem(collected_id, collector_id, count) => {
+                                                let mut state_lock = state.lock().await;
+                                                if Some(collector_id) != state_lock.my_entity_id {
+                                                    return Ok(());
+                                                }
+                                                // experience orbs are counted when the experience changes
+                                                if let Some(Object { item: Some((item_id, _)), .. }) = state_lock.objects.get(&collected_id) {
+                                                    let item_id = *item_id;
+                                                    *state_lock.picked_up.entry(item_id).or_insert(0) += count.0;
+                                                }
+                                            }
+                                            ClientBound::SetExperience(_, level, total) => {
+                                                let mut state_lock = state.lock().await;
+                                                // dying resets the experience, so only count what was gained
+                                                if let Some((old_level, old_total)) = state_lock.experience {
+                                                    state_lock.levels_gained += (level.0 - old_level).max(0);
+                                                    state_lock.experience_gained += (total.0 - old_total).max(0);
+                                                }
+                                                state_lock.experience = Some((level.0, total.0));
+                                            }
                                             ClientBound::SpawnLivingEntity(
                                                 entity_id,
                                                 _,
@@ -494,6 +509,34 @@ where
                                                     },
                                                 );
                                             }
+                                            ClientBound::PlayerInfo(players) => {
+                                                let mut state_lock = state.lock().await;
+                                                for (uuid, name) in players {
+                                                    state_lock.player_names.insert(uuid, name.0);
+                                                }
+                                            }
+                                            ClientBound::SpawnPlayer(entity_id, uuid, x, y, z) => {
+                                                let player_distance = settings.lock().await.player_distance;
+                                                let mut state_lock = state.lock().await;
+                                                // the tab list comes first, so the name should be known already
+                                                let name = match state_lock.player_names.get(&uuid) {
+                                                    Some(name) => name.clone(),
+                                                    None => "A player".to_string(),
+                                                };
+                                                state_lock.players.insert(
+                                                    entity_id,
+                                                    Player {
+                                                        entity_id,
+                                                        name,
+                                                        pos: (x, y, z),
+                                                        nearby: false,
+                                                    },
+                                                );
+                                                if let Some((name, distance)) = player_came_near(&mut state_lock, &entity_id, player_distance) {
+                                                    write_to_log(format!("{} is {:.0} blocks away.", name, distance));
+                                                    let _ = events.send(Event::PlayerNearby { name, distance });
+                                                }
+                                            }
                                             ClientBound::SetSlot(window_id, slot_id, slot_data) => {
                                                 if window_id == 0 && slot_id >= 36 && slot_id <= 44
                                                 {
@@ -503,18 +546,28 @@ where
                                                 }
                                             }
                                             ClientBound::DestroyEntities(ids) => {
-                                                // remove mobs
+                                                // remove mobs, players and objects
                                                 let mut state_lock = state.lock().await;
                                                 for id in ids {
                                                     state_lock.mobs.remove(&id);
+                                                    state_lock.players.remove(&id);
+                                                    state_lock.objects.remove(&id);
                                                 }
                                             }
                                             ClientBound::EntityTeleport(entity_id, x, y, z, ..) => {
-                                                // change mob position
-                                                if let Some(mob) =
-                                                    state.lock().await.mobs.get_mut(&entity_id)
-                                                {
+                                                // change mob, player or object position
+                                                let player_distance = settings.lock().await.player_distance;
+                                                let mut state_lock = state.lock().await;
+                                                if let Some(mob) = state_lock.mobs.get_mut(&entity_id) {
                                                     mob.pos = (x, y, z);
+                                                } else if let Some(player) = state_lock.players.get_mut(&entity_id) {
+                                                    player.pos = (x, y, z);
+                                                    if let Some((name, distance)) = player_came_near(&mut state_lock, &entity_id, player_distance) {
+                                                        write_to_log(format!("{} is {:.0} blocks away.", name, distance));
+                                                        let _ = events.send(Event::PlayerNearby { name, distance });
+                                                    }
+                                                } else if let Some(object) = state_lock.objects.get_mut(&entity_id) {
+                                                    object.pos = (x, y, z);
                                                 }
                                             }
                                             ClientBound::EntityPosition(
@@ -524,72 +577,226 @@ where
                                                 delta_z,
                                                 _,
                                             ) => {
-                                                // change mob position
+                                                // change mob, player or object position
                                                 // calculate the new position
-                                                if let Some(mob) =
-                                                    state.lock().await.mobs.get_mut(&entity_id)
-                                                {
-                                                    let new_pos = |old, delta| {
-                                                        ((delta as f64) / 128.0 + old * 32.0) / 32.0
-                                                    };
-                                                    mob.pos = (
-                                                        new_pos(mob.pos.0, delta_x),
-                                                        new_pos(mob.pos.1, delta_y),
-                                                        new_pos(mob.pos.2, delta_z),
-                                                    );
+                                                let new_pos = |old: (f64, f64, f64)| {
+                                                    let new = |old, delta| ((delta as f64) / 128.0 + old * 32.0) / 32.0;
+                                                    (new(old.0, delta_x), new(old.1, delta_y), new(old.2, delta_z))
+                                                };
+                                                let player_distance = settings.lock().await.player_distance;
+                                                let mut state_lock = state.lock().await;
+                                                if let Some(mob) = state_lock.mobs.get_mut(&entity_id) {
+                                                    mob.pos = new_pos(mob.pos);
+                                                } else if let Some(player) = state_lock.players.get_mut(&entity_id) {
+                                                    player.pos = new_pos(player.pos);
+                                                    if let Some((name, distance)) = player_came_near(&mut state_lock, &entity_id, player_distance) {
+                                                        write_to_log(format!("{} is {:.0} blocks away.", name, distance));
+                                                        let _ = events.send(Event::PlayerNearby { name, distance });
+                                                    }
+                                                } else if let Some(object) = state_lock.objects.get_mut(&entity_id) {
+                                                    object.pos = new_pos(object.pos);
                                                 }
                                             }
                                             ClientBound::PlayDisconnect(reason) => {
+                                                let _ = events.send(Event::Kicked(chat::to_plain_text(&reason.0)));
                                                 return Err(Error::new(
                                                     ErrorKind::Other,
                                                     format!("Kicked: {:?}", reason),
-                                                ))
+                                                ));
                                             }
                                             ClientBound::LoginDisconnect(reason) => {
+                                                let _ = events.send(Event::Kicked(chat::to_plain_text(&reason.0)));
                                                 return Err(Error::new(
                                                     ErrorKind::Other,
                                                     format!("Kicked: {:?}", reason),
-                                                ))
+                                                ));
                                             }
                                             ClientBound::Statistics(statistics) => {
                                                 let mut state_lock = state.lock().await;
                                                 if state_lock.initial_statistics.len() == 0 {
                                                     state_lock.initial_statistics = statistics.clone();
                                                     state_lock.statistics = statistics;
-                                                    update_statistics(format!("No statistics to show yet."));
+                                                    let _ = events.send(Event::StatisticsUpdated {
+                                                        rows: Vec::new(),
+                                                        text: "No statistics to show yet.".to_string(),
+                                                    });
                                                 } else {
-                                                    let mut statistics_str = String::new();
-                                                    for statistic in statistics {
-                                                        // we're only interested in the "killed" category
-                                                        if (statistic.0).0 != 6 {
-                                                            continue;
-                                                        }
-                                                        // get the old value
-                                                        let mut old = state_lock.statistics.iter_mut().find(|e| (e.0).0 == (statistic.0).0 && (e.1).0 == (statistic.1).0).unwrap();
-                                                        old.2 = statistic.2;
+                                                    let (killed, broken) = update_statistics(&mut state_lock, statistics);
+                                                    let total = kills(&state_lock);
+                                                    for (mob, count) in killed {
+                                                        let _ = events.send(Event::MobKilled { mob: registries::statistic_name(6, mob), count, total });
+                                                    }
+                                                    for item in broken {
+                                                        let item = registries::statistic_name(3, item);
+                                                        log_with_level(Level::Warn, format!("{} broke.", item));
+                                                        let _ = events.send(Event::ItemBroken(item));
+                                                    }
+                                                    let mut text = statistics_text(&state_lock);
+                                                    if text.is_empty() {
+                                                        text = "No statistics to show yet.".to_string();
                                                     }
-                                                    // update the gui
-                                                    for statistic in &state_lock.statistics {
-                                                        // again, we're only interested in the "killed" category
-                                                        if (statistic.0).0 != 6 {
-                                                            continue;
+                                                    let _ = events.send(Event::StatisticsUpdated { rows: export_rows(&state_lock), text });
+                                                }
+                                            },
+                                            ClientBound::ChatMessage(json, position, _) => {
+                                                // game info messages are shown above the hotbar and are not worth logging
+                                                if position == 2 {
+                                                    return Ok(());
+                                                }
+                                                let message = chat::to_plain_text(&json.0);
+                                                write_to_log(format!("Chat: {}", message));
+                                                let _ = events.send(Event::ChatReceived(message.clone()));
+
+                                                if let Some((sender, message)) = chat::whisper(&json.0, position) {
+                                                    // private messages starting with ! from the owners are commands
+                                                    let command = match remote::command(&settings.lock().await.owners, &sender, &message) {
+                                                        Some(command) => command,
+                                                        None => return Ok(()),
+                                                    };
+                                                    write_to_log(format!("Remote command from {}: {}", sender, message));
+
+                                                    let reply = match &command {
+                                                        Ok(Command::Status) => {
+                                                            let state_lock = state.lock().await;
+                                                            format!("health: {}/20, food: {}/20, kills: {}, drops: {}", state_lock.health, state_lock.food, kills(&state_lock), state_lock.dropped_items)
+                                                        }
+                                                        Ok(Command::Sneak(sneak)) => {
+                                                            settings.lock().await.sneak = *sneak;
+                                                            format!("Changed sneak to {}", sneak)
                                                         }
-                                                        // get the initial value
-                                                        let initial = state_lock.initial_statistics.iter().find(|e| (e.0).0 == (statistic.0).0 && (e.1).0 == (statistic.1).0).unwrap();
-                                                        // calculate the difference
-                                                        let difference = (statistic.2).0 - (initial.2).0;
-                                                        if difference == 0 {
-                                                            continue;
+                                                        Ok(Command::Attack(id, attack)) => {
+                                                            let mut settings_lock = settings.lock().await;
+                                                            settings_lock.attack_mobs.retain(|x| x != id);
+                                                            if *attack {
+                                                                settings_lock.attack_mobs.push(*id);
+                                                            }
+                                                            format!("Mob {} {}", MOBS.get_by_left(id).unwrap(), if *attack { "selected" } else { "unselected" })
                                                         }
-                                                        statistics_str += &format!("{}: {}\n", MOBS.get_by_left(&(statistic.1).0).unwrap_or(&"{UNKNOWN MOB}"), difference);
+                                                        Ok(Command::Eat) => {
+                                                            state.lock().await.eat_now = true;
+                                                            "Eating".to_string()
+                                                        }
+                                                        Ok(Command::Quit) => "Disconnecting".to_string(),
+                                                        Err(e) => e.clone(),
+                                                    };
+                                                    client
+                                                        .lock()
+                                                        .await
+                                                        .send(ServerBound::ChatMessage(MString(format!("/msg {} {}", sender, reply))))
+                                                        .await?;
+
+                                                    if let Ok(Command::Quit) = command {
+                                                        shutdown_sender.send(()).await.unwrap();
                                                     }
-                                                    if statistics_str.len() == 0 {
-                                                        update_statistics(format!("No statistics to show yet."));
-                                                    } else {
-                                                        update_statistics(statistics_str);
+                                                    return Ok(());
+                                                }
+
+                                                // log in if the server has an auth plugin and a password is stored for it
+                                                let password = settings.lock().await.login_passwords.get(&ip).cloned();
+                                                if let Some(password) = password {
+                                                    if let Some(command) = login_command(&message, position, &password) {
+                                                        let mut state_lock = state.lock().await;
+                                                        if state_lock.login_sent {
+                                                            return Ok(());
+                                                        }
+                                                        state_lock.login_sent = true;
+                                                        drop(state_lock);
+
+                                                        client
+                                                            .lock()
+                                                            .await
+                                                            .send(ServerBound::ChatMessage(MString(command.clone())))
+                                                            .await?;
+                                                        write_to_log(format!("Sent {:?}.", hide_password(&command)));
                                                     }
                                                 }
-                                            },
+                                            }
+                                            ClientBound::CombatEvent(_, Some((player_id, _, message))) => {
+                                                let mut state_lock = state.lock().await;
+                                                if state_lock.my_entity_id != Some(player_id) {
+                                                    return Ok(());
+                                                }
+                                                let pos = state_lock.my_pos;
+                                                state_lock.death_pos = Some(pos);
+                                                drop(state_lock);
+                                                let message = chat::to_plain_text(&message.0);
+                                                write_to_log(format!(
+                                                    "Died at {:.1} {:.1} {:.1}: {}",
+                                                    pos.0,
+                                                    pos.1,
+                                                    pos.2,
+                                                    message
+                                                ));
+                                                let _ = events.send(Event::Died { message, pos });
+
+                                                if settings.lock().await.auto_respawn {
+                                                    write_to_log("Respawning.".to_string());
+                                                    client
+                                                        .lock()
+                                                        .await
+                                                        .send(ServerBound::ClientStatus(VarInt(0)))
+                                                        .await?;
+                                                }
+                                            }
+                                            ClientBound::Respawn => {
+                                                // all entities will be sent again
+                                                let mut state_lock = state.lock().await;
+                                                state_lock.mobs.clear();
+                                                state_lock.players.clear();
+                                                state_lock.objects.clear();
+                                                // and the chunks, since it could be another dimension
+                                                state_lock.world.clear();
+                                                // the same packet changes dimensions, only a recorded death makes it a respawn
+                                                state_lock.respawned = state_lock.death_pos.is_some();
+                                            }
+                                            ClientBound::ChunkData(x, z, full_chunk, primary_bit_mask, data) => {
+                                                match Chunk::parse(primary_bit_mask.0, data).await {
+                                                    Ok(chunk) => state.lock().await.world.load_chunk(x, z, full_chunk, chunk),
+                                                    // not worth disconnecting over
+                                                    Err(e) => log_with_level(Level::Warn, format!("Couldn't parse chunk {} {}: {}", x, z, e)),
+                                                }
+                                            }
+                                            ClientBound::UnloadChunk(x, z) => {
+                                                state.lock().await.world.unload_chunk(x, z);
+                                            }
+                                            ClientBound::BlockChange(position, block) => {
+                                                let (x, y, z) = world::decode_position(position);
+                                                state.lock().await.world.set_block(x, y, z, block.0 as u32);
+                                            }
+                                            ClientBound::MultiBlockChange(section_position, blocks) => {
+                                                let (section_x, section_y, section_z) = world::decode_section_position(section_position);
+                                                let mut state_lock = state.lock().await;
+                                                for block in blocks {
+                                                    state_lock.world.set_block(
+                                                        section_x * 16 + ((block.0 >> 8) & 15) as i32,
+                                                        section_y * 16 + (block.0 & 15) as i32,
+                                                        section_z * 16 + ((block.0 >> 4) & 15) as i32,
+                                                        (block.0 >> 12) as u32,
+                                                    );
+                                                }
+                                            }
+                                            ClientBound::EntityVelocity(entity_id, x, y, z) => {
+                                                let mut state_lock = state.lock().await;
+                                                let velocity = (x as f64 / 8000.0, y as f64 / 8000.0, z as f64 / 8000.0);
+                                                if Some(entity_id) == state_lock.my_entity_id {
+                                                    state_lock.velocity = velocity;
+                                                } else if let Some(object) = state_lock.objects.get_mut(&entity_id) {
+                                                    object.velocity = velocity;
+                                                    let (kind, pos) = (object.kind, object.pos);
+                                                    if (kind == Kind::Arrow || kind == Kind::Fireball) && dodge(&mut state_lock, pos, velocity) {
+                                                        write_to_log("Dodging a projectile.".to_string());
+                                                    }
+                                                }
+                                            }
+                                            ClientBound::Explosion(x, y, z, destroyed, motion_x, motion_y, motion_z) => {
+                                                let mut state_lock = state.lock().await;
+                                                let (x, y, z) = (x.floor() as i32, y.floor() as i32, z.floor() as i32);
+                                                for (dx, dy, dz) in destroyed {
+                                                    state_lock.world.set_block(x + dx as i32, y + dy as i32, z + dz as i32, 0);
+                                                }
+                                                let velocity = state_lock.velocity;
+                                                state_lock.velocity = (velocity.0 + motion_x as f64, velocity.1 + motion_y as f64, velocity.2 + motion_z as f64);
+                                            }
                                             ClientBound::Unknown(_) => {}
                                             _other => {
                                                 // println!("Received {:?}", other);
@@ -601,7 +808,14 @@ where
                                 )
                                 .await;
                             }
-                        });
+                        };
+                        if ordered {
+                            // finished before the next packet is read
+                            process.await;
+                        } else {
+                            // spawn a task for proccessing this packet
+                            tokio::spawn(process);
+                        }
                     }
                 }
                 .await,
@@ -610,12 +824,799 @@ where
         }
     });
 
+    // spawn a task for sending scheduled chat messages
+    let task6 = tokio::spawn({
+        clone_all![client, state, settings, write_to_log, log_with_level, shutdown_sender];
+        async move {
+            // the compiler desires this to be put into a variable for some reason
+            let log_with_level_clone = log_with_level.clone();
+            check(
+                log_with_level_clone,
+                client.clone(),
+                state.clone(),
+                shutdown_sender,
+                async move {
+                    let mut joined_at = None;
+                    let mut last_sent: HashMap<ScheduledMessage, Instant> = HashMap::new();
+                    loop {
+                        tokio::time::sleep(std::time::Duration::from_secs_f32(1.0)).await;
+                        if state.lock().await.my_entity_id.is_none() {
+                            continue;
+                        }
+                        let joined_at = *joined_at.get_or_insert_with(Instant::now);
+                        // give auth plugins some time to log us in first
+                        if joined_at.elapsed() < std::time::Duration::from_secs(5) {
+                            continue;
+                        }
+
+                        let scheduled_messages = settings.lock().await.scheduled_messages.clone();
+                        for scheduled in scheduled_messages {
+                            let last = last_sent.get(&scheduled);
+                            let due = match scheduled.schedule {
+                                Schedule::Every(interval) => {
+                                    last.unwrap_or(&joined_at).elapsed() >= interval
+                                }
+                                Schedule::At(time) => {
+                                    let since = Local::now().time() - time;
+                                    since >= chrono::Duration::zero()
+                                        && since < chrono::Duration::minutes(1)
+                                        && last.is_none_or(|last| {
+                                            last.elapsed() > std::time::Duration::from_secs(60)
+                                        })
+                                }
+                                Schedule::OnJoin => last.is_none(),
+                            };
+                            if !due {
+                                continue;
+                            }
+
+                            client
+                                .lock()
+                                .await
+                                .send(ServerBound::ChatMessage(MString(scheduled.message.clone())))
+                                .await?;
+                            write_to_log(format!(
+                                "Sent scheduled message {:?}.",
+                                scheduled.message
+                            ));
+                            last_sent.insert(scheduled, Instant::now());
+                        }
+                    }
+                }
+                .await,
+            )
+            .await;
+        }
+    });
+
+    // spawn a task for moving around a bit, so that the server doesn't kick us for being AFK
+    let task7 = tokio::spawn({
+        clone_all![client, state, settings, log_with_level, shutdown_sender];
+        async move {
+            check(
+                log_with_level,
+                client.clone(),
+                state.clone(),
+                shutdown_sender,
+                async move {
+                    loop {
+                        tokio::time::sleep(std::time::Duration::from_secs_f32(
+                            anti_afk::next_delay(),
+                        ))
+                        .await;
+                        if !settings.lock().await.anti_afk {
+                            continue;
+                        }
+                        let state_lock = state.lock().await;
+                        if state_lock.my_entity_id.is_none() {
+                            continue;
+                        }
+                        let (rotation, on_ground) = (state_lock.my_rotation, state_lock.on_ground);
+                        drop(state_lock);
+
+                        // movement is left to the physics task, which sends the position
+                        match anti_afk::random_action(rotation) {
+                            Action::Look(yaw, pitch, seconds) => {
+                                client
+                                    .lock()
+                                    .await
+                                    .send(ServerBound::PlayerRotation(yaw, pitch, on_ground))
+                                    .await?;
+                                tokio::time::sleep(std::time::Duration::from_secs_f32(seconds))
+                                    .await;
+                                let on_ground = state.lock().await.on_ground;
+                                client
+                                    .lock()
+                                    .await
+                                    .send(ServerBound::PlayerRotation(
+                                        rotation.0, rotation.1, on_ground,
+                                    ))
+                                    .await?;
+                            }
+                            Action::Swing => {
+                                client
+                                    .lock()
+                                    .await
+                                    .send(ServerBound::Animation(VarInt(0)))
+                                    .await?;
+                            }
+                            Action::Jump => {
+                                let mut state_lock = state.lock().await;
+                                if state_lock.on_ground {
+                                    state_lock.velocity.1 = 0.42;
+                                }
+                            }
+                            Action::Step(x, z) => {
+                                let mut state_lock = state.lock().await;
+                                state_lock.velocity.0 += x;
+                                state_lock.velocity.2 += z;
+                                drop(state_lock);
+                                tokio::time::sleep(std::time::Duration::from_secs_f32(0.3)).await;
+                                // and back
+                                let mut state_lock = state.lock().await;
+                                state_lock.velocity.0 -= x;
+                                state_lock.velocity.2 -= z;
+                            }
+                        }
+                    }
+                }
+                .await,
+            )
+            .await;
+        }
+    });
+
+    // spawn a task for simulating physics every tick and sending the position to the server
+    let task8 = tokio::spawn({
+        clone_all![client, state, settings, write_to_log, log_with_level, shutdown_sender];
+        async move {
+            let log_with_level_clone = log_with_level.clone();
+            check(
+                log_with_level_clone,
+                client.clone(),
+                state.clone(),
+                shutdown_sender,
+                async move {
+                    let mut ticks_since_sent = 0;
+                    // for noticing when walking back to the AFK spot doesn't work
+                    let mut closest_distance = f64::INFINITY;
+                    let mut ticks_stuck = 0;
+                    // the AFK spot and the position the bot gave up walking back from
+                    let mut given_up_on = None;
+                    let mut ticks_since_given_up = 0;
+                    // longer after each failed try, so that a blocked way doesn't fill the log
+                    let mut retry_ticks = 600;
+                    // where the bot is going to and the blocks to walk through to get there
+                    let mut goal: Option<(i32, i32, i32)> = None;
+                    let mut path = Vec::new();
+                    // the path being searched for, on another thread
+                    let mut search: Option<oneshot::Receiver<Option<pathfinding::Path>>> = None;
+                    let mut ticks_on_waypoint = 0;
+                    let mut ticks_since_join = 0;
+                    loop {
+                        tokio::time::sleep(std::time::Duration::from_secs_f32(0.05)).await;
+                        let mut state_lock = state.lock().await;
+                        if !state_lock.position_received {
+                            continue;
+                        }
+
+                        // start going somewhere when asked to from the GUI, or after joining
+                        // (giving the server some time to send the chunks first)
+                        ticks_since_join += 1;
+                        let mut settings_lock = settings.lock().await;
+                        if settings_lock.goto_now
+                            || (ticks_since_join == 100 && settings_lock.goto_on_join)
+                        {
+                            settings_lock.goto_now = false;
+                            if let Some((x, y, z)) = settings_lock.goto {
+                                write_to_log(format!("Going to {} {} {}.", x, y, z));
+                                goal = Some((x, y, z));
+                                path.clear();
+                                search = None;
+                                ticks_on_waypoint = 0;
+                            }
+                        }
+                        let return_to_spot = settings_lock.return_to_spot;
+                        drop(settings_lock);
+
+                        if let Some((x, y, z)) = goal {
+                            let feet = (
+                                state_lock.my_pos.0.floor() as i32,
+                                state_lock.my_pos.1.floor() as i32,
+                                state_lock.my_pos.2.floor() as i32,
+                            );
+                            if path.is_empty() {
+                                // being a block above or below is close enough, in case the goal was the ground
+                                if (feet.0, feet.2) == (x, z) && (feet.1 - y).abs() <= 1 {
+                                    write_to_log(format!(
+                                        "Arrived at {} {} {}, it's the new AFK spot.",
+                                        x, y, z
+                                    ));
+                                    state_lock.anchor = Some(state_lock.my_pos);
+                                    goal = None;
+                                } else if let Some(receiver) = &mut search {
+                                    match receiver.try_recv() {
+                                        Ok(Some(new_path)) => {
+                                            path = new_path;
+                                            search = None;
+                                        }
+                                        Ok(None) => {
+                                            write_to_log(format!(
+                                                "Can't find a way to {} {} {}.",
+                                                x, y, z
+                                            ));
+                                            goal = None;
+                                            search = None;
+                                        }
+                                        // still searching
+                                        Err(TryRecvError::Empty) => {}
+                                        // the search panicked, it's tried again
+                                        Err(TryRecvError::Closed) => search = None,
+                                    }
+                                } else if state_lock.world.block(feet.0, feet.1, feet.2).is_some() {
+                                    // the search can take a while, so it's done with a copy of the world
+                                    // without holding the state, and the path is followed on a later tick
+                                    // if the goal is too far away to be loaded, this only gets us closer,
+                                    // and the search is repeated from there
+                                    let (sender, receiver) = oneshot::channel();
+                                    let world = state_lock.world.clone();
+                                    tokio::task::spawn_blocking(move || {
+                                        // fails if the goal was changed meanwhile
+                                        let _ = sender.send(pathfinding::find_path(&world, feet, (x, y, z)));
+                                    });
+                                    search = Some(receiver);
+                                }
+                            }
+
+                            if let Some(&(next_x, next_y, next_z)) = path.first() {
+                                let target =
+                                    (next_x as f64 + 0.5, next_y as f64, next_z as f64 + 0.5);
+                                let State {
+                                    my_pos,
+                                    velocity,
+                                    on_ground,
+                                    ..
+                                } = &mut *state_lock;
+                                physics::walk_towards(*my_pos, target, velocity, *on_ground);
+                                if target.1 > my_pos.1 + 0.5 && *on_ground {
+                                    velocity.1 = 0.42;
+                                }
+
+                                if physics::horizontal_distance(*my_pos, target) < 0.3
+                                    && (my_pos.1 - target.1).abs() < 0.5
+                                {
+                                    path.remove(0);
+                                    ticks_on_waypoint = 0;
+                                } else {
+                                    ticks_on_waypoint += 1;
+                                    if ticks_on_waypoint >= 100 {
+                                        write_to_log(format!(
+                                            "Got stuck on the way to {} {} {}.",
+                                            x, y, z
+                                        ));
+                                        goal = None;
+                                        path.clear();
+                                    }
+                                }
+                            }
+                        }
+
+                        // walk back to the AFK spot after being knocked away
+                        let anchor = state_lock.anchor;
+                        if let (true, None, Some(anchor)) = (return_to_spot, goal, anchor) {
+                            let distance = physics::horizontal_distance(state_lock.my_pos, anchor);
+                            if let Some((spot, from)) = given_up_on {
+                                // try again after a while, or as soon as something changed
+                                ticks_since_given_up += 1;
+                                let pos = state_lock.my_pos;
+                                let moved = physics::horizontal_distance(pos, from) > 1.0
+                                    || (pos.1 - from.1).abs() > 1.0;
+                                if spot != anchor || moved || ticks_since_given_up >= retry_ticks {
+                                    closest_distance = f64::INFINITY;
+                                    ticks_stuck = 0;
+                                    given_up_on = None;
+                                }
+                                if spot != anchor {
+                                    retry_ticks = 600;
+                                }
+                            }
+                            if distance < 0.2 {
+                                closest_distance = f64::INFINITY;
+                                ticks_stuck = 0;
+                                given_up_on = None;
+                                retry_ticks = 600;
+                            } else if distance > 0.5 && given_up_on.is_none() {
+                                let State {
+                                    my_pos,
+                                    velocity,
+                                    on_ground,
+                                    ..
+                                } = &mut *state_lock;
+                                physics::walk_towards(*my_pos, anchor, velocity, *on_ground);
+
+                                if distance < closest_distance - 0.05 {
+                                    closest_distance = distance;
+                                    ticks_stuck = 0;
+                                } else {
+                                    ticks_stuck += 1;
+                                }
+                                // there might be a block in the way that can be jumped on
+                                if ticks_stuck % 20 == 10 && state_lock.on_ground {
+                                    state_lock.velocity.1 = 0.42;
+                                }
+                                if ticks_stuck >= 100 {
+                                    given_up_on = Some((anchor, state_lock.my_pos));
+                                    ticks_since_given_up = 0;
+                                    write_to_log(format!(
+                                        "Can't walk back to the AFK spot, {:.0} blocks away. Trying again in {} seconds.",
+                                        distance,
+                                        retry_ticks / 20
+                                    ));
+                                    retry_ticks = (retry_ticks * 2).min(12000);
+                                }
+                            }
+                        }
+
+                        let old = (state_lock.my_pos, state_lock.on_ground);
+                        let State {
+                            world,
+                            my_pos,
+                            velocity,
+                            on_ground,
+                            ..
+                        } = &mut *state_lock;
+                        physics::tick(world, my_pos, velocity, on_ground);
+
+                        // vanilla sends the position at least once a second, even when not moving
+                        ticks_since_sent += 1;
+                        if (state_lock.my_pos, state_lock.on_ground) != old
+                            || ticks_since_sent >= 20
+                        {
+                            ticks_since_sent = 0;
+                            let (x, y, z) = state_lock.my_pos;
+                            client
+                                .lock()
+                                .await
+                                .send(ServerBound::PlayerPosition(x, y, z, state_lock.on_ground))
+                                .await?;
+                        }
+                    }
+                }
+                .await,
+            )
+            .await;
+        }
+    });
+
+    // spawn a task for writing the statistics to a file every once in a while
+    let task9 = tokio::spawn({
+        clone_all![state, settings, log_with_level, ip];
+        async move {
+            loop {
+                tokio::time::sleep(export::INTERVAL).await;
+                let path = settings.lock().await.statistics_file.clone();
+                if path.is_empty() {
+                    continue;
+                }
+                let rows = export_rows(&*state.lock().await);
+                // not worth disconnecting over
+                if let Err(e) = export::append(&path, &ip, &rows).await {
+                    log_with_level(Level::Warn, format!("Couldn't write the statistics to {}: {}", path, e));
+                }
+            }
+        }
+    });
+
+    // spawn a task for the requests sent with the Bot handle
+    let task10 = tokio::spawn({
+        clone_all![client, state, log_with_level, shutdown_sender];
+        async move {
+            check(
+                log_with_level,
+                client.clone(),
+                state.clone(),
+                shutdown_sender,
+                async move {
+                    while let Some(request) = requests.recv().await {
+                        match request {
+                            Request::Chat(message) => {
+                                client
+                                    .lock()
+                                    .await
+                                    .send(ServerBound::ChatMessage(MString(message)))
+                                    .await?;
+                            }
+                            Request::Eat => state.lock().await.eat_now = true,
+                        }
+                    }
+                    Ok(())
+                }
+                .await,
+            )
+            .await;
+        }
+    });
+
     shutdown_receiver.recv().await;
-    task1.abort();
     task2.abort();
-    task3.abort();
-    task4.abort();
     task5.abort();
+    task6.abort();
+    task7.abort();
+    task8.abort();
+    task9.abort();
+    task10.abort();
+
+    // let the behaviors finish up
+    for hook in hooks.iter() {
+        let _ = hook.send(Hook::Disconnect);
+    }
+    for task in behavior_tasks {
+        let _ = task.await;
+    }
+
+    // write the statistics one last time, so that nothing since the last time is lost
+    let path = settings.lock().await.statistics_file.clone();
+    if !path.is_empty() {
+        let rows = export_rows(&*state.lock().await);
+        match export::append(&path, &ip, &rows).await {
+            Ok(()) => write_to_log(format!("Saved the statistics to {}", path)),
+            Err(e) => log_with_level(Level::Warn, format!("Couldn't write the statistics to {}: {}", path, e)),
+        }
+    }
+
+    // nothing set means that the disconnect was asked for, by the user or an owner
+    let disconnect = state.lock().await.disconnect;
+    Ok(disconnect.unwrap_or(Disconnect::Requested))
+}
+
+// the packets that change the world or the dropped items are handled one at a time, in the order they came in,
+// so that e.g. a respawn doesn't clear the chunks of the new dimension, a block change doesn't come before its chunk,
+// and an item is known before it's picked up
+fn is_ordered(packet: &ClientBound) -> bool {
+    matches!(
+        packet,
+        ClientBound::Respawn
+            | ClientBound::ChunkData(..)
+            | ClientBound::UnloadChunk(..)
+            | ClientBound::BlockChange(..)
+            | ClientBound::MultiBlockChange(..)
+            | ClientBound::Explosion(..)
+            | ClientBound::SpawnEntity(..)
+            | ClientBound::EntityMetadata(..)
+            | ClientBound::CollectItem(..)
+            | ClientBound::DestroyEntities(..)
+    )
+}
+
+// returns the command to send if the message is a login or register prompt of an auth plugin
+// the prompts are system messages, so that players can't send them
+fn login_command(message: &str, position: u8, password: &str) -> Option<String> {
+    if position != 1 {
+        return None;
+    }
+    let message = message.to_lowercase();
+    if is_prompt(&message, "/register") {
+        Some(format!("/register {} {}", password, password))
+    } else if is_prompt(&message, "/login") {
+        Some(format!("/login {}", password))
+    } else {
+        None
+    }
+}
+
+// auth plugins show the command with a placeholder for the password, like "/login <password>"
+fn is_prompt(message: &str, command: &str) -> bool {
+    message.match_indices(command).any(|(i, _)| {
+        let rest = message[i + command.len()..].trim_start();
+        rest.starts_with('<') || rest.starts_with('[') || rest.starts_with('(') || rest.starts_with("pass")
+    })
+}
+
+// the login command as it can be shown in the log, never with the actual password
+fn hide_password(command: &str) -> String {
+    match command.find(' ') {
+        Some(i) => format!("{} ********", &command[..i]),
+        None => command.to_string(),
+    }
+}
+
+// stores the new values of the statistics, returns the mob types killed since the last update with
+// how many of each, and the items that broke
+fn update_statistics(state: &mut State, statistics: Vec<(VarInt, VarInt, VarInt)>) -> (Vec<(i64, i64)>, Vec<i64>) {
+    let mut killed = Vec::new();
+    let mut broken = Vec::new();
+    for statistic in statistics {
+        // update the old value
+        let old = state.statistics.iter_mut().find(|e| e.0 == statistic.0 && e.1 == statistic.1);
+        let old_value = old.as_ref().map(|old| (old.2).0).unwrap_or(0);
+        if (statistic.2).0 > old_value {
+            // the categories are in registries::CATEGORIES
+            match (statistic.0).0 {
+                6 => killed.push(((statistic.1).0, (statistic.2).0 - old_value)),
+                3 => broken.push((statistic.1).0),
+                _ => {}
+            }
+        }
+        match old {
+            Some(old) => old.2 = statistic.2,
+            // a statistic that wasn't counted before, its initial value is 0
+            None => state.statistics.push(statistic),
+        }
+    }
+    (killed, broken)
+}
+
+// the number of mobs killed since connecting
+fn kills(state: &State) -> i64 {
+    state
+        .statistics
+        .iter()
+        .filter(|statistic| (statistic.0).0 == 6)
+        .map(|statistic| {
+            let initial = state
+                .initial_statistics
+                .iter()
+                .find(|e| e.0 == statistic.0 && e.1 == statistic.1)
+                .map(|e| (e.2).0)
+                .unwrap_or(0);
+            (statistic.2).0 - initial
+        })
+        .sum()
+}
 
-    Ok(())
+// the mob names and how many of each were killed since joining
+pub(crate) fn kills_by_mob(state: &State) -> Vec<(String, i64)> {
+    statistics_since_join(state)
+        .remove(&6)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(mob, kills)| (registries::statistic_name(6, mob), kills))
+        .collect()
+}
+
+// the statistics that changed since joining, grouped by category, the most common things first
+fn statistics_since_join(state: &State) -> BTreeMap<i64, Vec<(i64, i64)>> {
+    let mut categories: BTreeMap<i64, Vec<(i64, i64)>> = BTreeMap::new();
+    for statistic in &state.statistics {
+        // calculate the difference from the initial value
+        let initial = state
+            .initial_statistics
+            .iter()
+            .find(|e| e.0 == statistic.0 && e.1 == statistic.1)
+            .map(|e| (e.2).0)
+            .unwrap_or(0);
+        let difference = (statistic.2).0 - initial;
+        if difference == 0 {
+            continue;
+        }
+        categories
+            .entry((statistic.0).0)
+            .or_default()
+            .push(((statistic.1).0, difference));
+    }
+    // some servers don't count picked up items, so show the collected ones instead
+    if !categories.contains_key(&4) && !state.picked_up.is_empty() {
+        categories.insert(
+            4,
+            state
+                .picked_up
+                .iter()
+                .map(|(&id, &count)| (id, count))
+                .collect(),
+        );
+    }
+
+    for statistics in categories.values_mut() {
+        statistics.sort_by_key(|&(_, difference)| -difference);
+    }
+
+    categories
+}
+
+// the rows to write to the statistics file
+fn export_rows(state: &State) -> Vec<export::Row> {
+    let row = |category: &str, statistic: String, count| export::Row {
+        category: category.to_string(),
+        statistic,
+        count,
+    };
+
+    let mut rows = Vec::new();
+    for (category, statistics) in statistics_since_join(state) {
+        for (id, difference) in statistics {
+            rows.push(row(
+                &registries::category_name(category),
+                registries::statistic_name(category, id),
+                difference,
+            ));
+        }
+    }
+    rows.push(row("Experience", "Levels".to_string(), state.levels_gained));
+    rows.push(row(
+        "Experience",
+        "Points".to_string(),
+        state.experience_gained,
+    ));
+    rows.push(row(
+        "Dropped nearby",
+        "Items".to_string(),
+        state.dropped_items as i64,
+    ));
+    rows.push(row(
+        "Dropped nearby",
+        "Experience".to_string(),
+        state.dropped_experience,
+    ));
+
+    rows
+}
+
+// the text of the statistics tab, everything since joining
+fn statistics_text(state: &State) -> String {
+    let hours = state.joined_at.elapsed().as_secs_f64() / 3600.0;
+    let per_hour = |amount: i64| amount as f64 / hours;
+    let mut text = String::new();
+
+    for (category, statistics) in statistics_since_join(state) {
+        text += &format!("{}:\n", registries::category_name(category));
+        for (id, difference) in statistics {
+            text += &format!(
+                "  {}: {} ({:.0}/hour)\n",
+                registries::statistic_name(category, id),
+                difference,
+                per_hour(difference)
+            );
+        }
+    }
+
+    if state.experience_gained > 0 {
+        text += &format!(
+            "Experience: {} levels, {} points ({:.0}/hour)\n",
+            state.levels_gained,
+            state.experience_gained,
+            per_hour(state.experience_gained)
+        );
+    }
+
+    if state.dropped_items > 0 || state.dropped_experience > 0 {
+        text += &format!(
+            "Dropped nearby: {} items, {} experience\n",
+            state.dropped_items, state.dropped_experience
+        );
+    }
+
+    text
+}
+
+// steps aside if the projectile is about to hit the bot, returns whether it did
+fn dodge(state: &mut State, pos: (f64, f64, f64), velocity: (f64, f64, f64)) -> bool {
+    let center = (state.my_pos.0, state.my_pos.1 + 0.9, state.my_pos.2);
+    let (ticks, distance) = objects::closest_approach(pos, velocity, center);
+    if ticks == 0.0 || ticks > 30.0 || distance > 1.0 || !state.on_ground {
+        return false;
+    }
+
+    // step sideways, to the side of the line that the bot is already on
+    let speed = (velocity.0.powi(2) + velocity.2.powi(2)).sqrt();
+    if speed == 0.0 {
+        return false;
+    }
+    let mut side = (-velocity.2 / speed, velocity.0 / speed);
+    let offset = (
+        center.0 - (pos.0 + velocity.0 * ticks),
+        center.2 - (pos.2 + velocity.2 * ticks),
+    );
+    if side.0 * offset.0 + side.1 * offset.1 < 0.0 {
+        side = (-side.0, -side.1);
+    }
+    state.velocity.0 += side.0 * 0.4;
+    state.velocity.2 += side.1 * 0.4;
+
+    true
+}
+
+// the name and distance of the player if they've just come closer than the distance from the settings,
+// they're only reported again after walking away
+fn player_came_near(state: &mut State, entity_id: &VarInt, player_distance: f64) -> Option<(String, f64)> {
+    let my_pos = state.my_pos;
+    let player = state.players.get_mut(entity_id)?;
+    let distance = ((player.pos.0 - my_pos.0).powi(2)
+        + (player.pos.1 - my_pos.1).powi(2)
+        + (player.pos.2 - my_pos.2).powi(2))
+    .sqrt();
+    if player.nearby {
+        // a few blocks further, so that walking along the edge doesn't report them over and over
+        if distance > player_distance + 4.0 {
+            player.nearby = false;
+        }
+        return None;
+    }
+    if player_distance <= 0.0 || distance >= player_distance {
+        return None;
+    }
+    player.nearby = true;
+    Some((player.name.clone(), distance))
+}
+
+// whether any part of the mob's hitbox can be seen from the bot's eyes
+pub(crate) fn can_see(state: &State, mob: &Mob) -> bool {
+    let eye_height = if state.sneaking { 1.27 } else { 1.62 };
+    let eyes = (state.my_pos.0, state.my_pos.1 + eye_height, state.my_pos.2);
+    let height = mobs::height(mob.entity_type.0);
+
+    [0.1, 0.5, 0.9].iter().any(|part| {
+        state
+            .world
+            .line_of_sight(eyes, (mob.pos.0, mob.pos.1 + height * part, mob.pos.2))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statistic(category: i64, id: i64, value: i64) -> (VarInt, VarInt, VarInt) {
+        (VarInt(category), VarInt(id), VarInt(value))
+    }
+
+    #[test]
+    fn only_broken_items_are_reported_as_broken() {
+        let mut state = State::new();
+        state.statistics = vec![statistic(3, 603, 1), statistic(5, 576, 10), statistic(6, 102, 4)];
+        state.initial_statistics = state.statistics.clone();
+
+        // a diamond sword broke, some coal was dropped and a zombie killed
+        let (killed, broken) = update_statistics(&mut state, vec![statistic(3, 603, 2), statistic(5, 576, 12), statistic(6, 102, 5)]);
+        assert_eq!(broken, vec![603]);
+        assert_eq!(killed, vec![(102, 1)]);
+        assert_eq!(registries::statistic_name(3, broken[0]), "Diamond Sword");
+
+        // only dropping things doesn't break anything
+        let (killed, broken) = update_statistics(&mut state, vec![statistic(5, 576, 13), statistic(5, 1, 1)]);
+        assert!(broken.is_empty());
+        assert!(killed.is_empty());
+        assert_eq!(kills(&state), 1);
+    }
+
+    #[test]
+    fn login_prompts() {
+        assert_eq!(login_command("Please log in with /login <password>", 1, "secret"), Some("/login secret".to_string()));
+        assert_eq!(login_command("Use /LOGIN [password] to continue", 1, "secret"), Some("/login secret".to_string()));
+        assert_eq!(
+            login_command("Register with /register <password> <password>", 1, "secret"),
+            Some("/register secret secret".to_string())
+        );
+        // register prompts often mention logging in too
+        assert_eq!(
+            login_command("/register (password) (confirm), or /login (password) if you have an account", 1, "secret"),
+            Some("/register secret secret".to_string())
+        );
+    }
+
+    #[test]
+    fn other_messages() {
+        assert_eq!(login_command("Welcome back!", 1, "secret"), None);
+        assert_eq!(login_command("You logged in, use /logout to leave", 1, "secret"), None);
+        assert_eq!(login_command("Run /login first", 1, "secret"), None);
+        assert!(!is_prompt("/login", "/login"));
+        assert!(is_prompt("/login password", "/login"));
+    }
+
+    #[test]
+    fn players_mentioning_login() {
+        assert_eq!(login_command("<Steve> did you /login yet?", 1, "secret"), None);
+        // only the server can prompt, even if a player copies the prompt
+        assert_eq!(login_command("<Steve> /login <password>", 0, "secret"), None);
+        assert_eq!(login_command("Please log in with /login <password>", 2, "secret"), None);
+    }
+
+    #[test]
+    fn password_is_hidden() {
+        assert_eq!(hide_password("/login secret"), "/login ********");
+        assert_eq!(hide_password("/register secret secret"), "/register ********");
+        // even when the password is part of the command
+        let command = login_command("/login <password>", 1, "login").unwrap();
+        assert_eq!(hide_password(&command), "/login ********");
+        assert!(!hide_password("/register hunter2 hunter2").contains("hunter2"));
+    }
 }
@@ -0,0 +1,166 @@
+// converts minecraft's JSON chat components to plain text
+// all info available on https://wiki.vg/index.php?title=Chat&oldid=16143
+
+use serde_json::Value;
+
+pub fn to_plain_text(json: &str) -> String {
+    match serde_json::from_str::<Value>(json) {
+        Ok(component) => {
+            let mut text = String::new();
+            append_component(&component, &mut text);
+            text
+        }
+        // not valid JSON, so just show it as it is
+        Err(_) => json.to_string(),
+    }
+}
+
+// returns the sender and the message if the chat message is a private message to us
+// they're system messages (position 1), so that a player can't fake one in the public chat
+pub fn whisper(json: &str, position: u8) -> Option<(String, String)> {
+    if position != 1 {
+        return None;
+    }
+    let component: Value = serde_json::from_str(json).ok()?;
+
+    // vanilla /msg
+    if component.get("translate").and_then(Value::as_str)
+        == Some("commands.message.display.incoming")
+    {
+        let with = component.get("with")?.as_array()?;
+        let mut sender = String::new();
+        let mut message = String::new();
+        append_component(with.first()?, &mut sender);
+        append_component(with.get(1)?, &mut message);
+
+        return Some((sender, message));
+    }
+
+    // plugins like Essentials format it as "[sender -> me] message"
+    let text = to_plain_text(json);
+    let text = text.strip_prefix('[')?;
+    let (header, message) = text.split_at(text.find("] ")?);
+    let (sender, receiver) = header.split_at(header.find(" -> ")?);
+    if receiver != " -> me" {
+        return None;
+    }
+
+    Some((sender.to_string(), message[2..].to_string()))
+}
+
+fn append_component(component: &Value, output: &mut String) {
+    match component {
+        Value::String(s) => *output += s,
+        Value::Array(components) => {
+            for component in components {
+                append_component(component, output);
+            }
+        }
+        Value::Object(map) => {
+            if let Some(Value::String(text)) = map.get("text") {
+                *output += text;
+            } else if let Some(Value::String(key)) = map.get("translate") {
+                let with: Vec<String> = match map.get("with") {
+                    Some(Value::Array(args)) => args
+                        .iter()
+                        .map(|arg| {
+                            let mut text = String::new();
+                            append_component(arg, &mut text);
+                            text
+                        })
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                *output += &translate(key, &with);
+            }
+            if let Some(Value::Array(extra)) = map.get("extra") {
+                for component in extra {
+                    append_component(component, output);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+// only the translation keys that are likely to be seen while AFK are known,
+// the rest are shown as the key followed by the arguments
+fn translate(key: &str, with: &[String]) -> String {
+    let format = match key {
+        "chat.type.text" => "<%s> %s",
+        "chat.type.announcement" => "[%s] %s",
+        "chat.type.emote" => "* %s %s",
+        "commands.message.display.incoming" => "%s whispers to you: %s",
+        "commands.message.display.outgoing" => "You whisper to %s: %s",
+        "multiplayer.player.joined" => "%s joined the game",
+        "multiplayer.player.left" => "%s left the game",
+        _ => return format!("{} {}", key, with.join(" ")).trim_end().to_string(),
+    };
+
+    let mut result = String::new();
+    let mut args = with.iter();
+    let mut parts = format.split("%s");
+    if let Some(first) = parts.next() {
+        result += first;
+    }
+    for part in parts {
+        if let Some(arg) = args.next() {
+            result += arg;
+        }
+        result += part;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_text() {
+        assert_eq!(
+            to_plain_text(r#"{"text":"Hello ","extra":[{"text":"world","bold":true},"!"]}"#),
+            "Hello world!"
+        );
+        assert_eq!(
+            to_plain_text(r#"{"translate":"chat.type.text","with":["Steve","hi"]}"#),
+            "<Steve> hi"
+        );
+        assert_eq!(to_plain_text("not json"), "not json");
+    }
+
+    #[test]
+    fn vanilla_whisper() {
+        let json = r#"{"translate":"commands.message.display.incoming","with":[{"text":"Owner"},{"text":"!status"}]}"#;
+        assert_eq!(
+            whisper(json, 1),
+            Some(("Owner".to_string(), "!status".to_string()))
+        );
+    }
+
+    #[test]
+    fn plugin_whisper() {
+        let json = r#"{"text":"","extra":[{"text":"[Owner -> me] "},{"text":"!sneak on"}]}"#;
+        assert_eq!(
+            whisper(json, 1),
+            Some(("Owner".to_string(), "!sneak on".to_string()))
+        );
+        // to someone else
+        assert_eq!(whisper(r#"{"text":"[Owner -> Steve] hi"}"#, 1), None);
+    }
+
+    #[test]
+    fn not_whispers() {
+        assert_eq!(whisper(r#"{"text":"Welcome to the server"}"#, 1), None);
+        assert_eq!(whisper("not json", 1), None);
+        // the whisper format typed into the public chat
+        let chat = r#"{"translate":"chat.type.text","with":["Mallory","[Owner -> me] !quit"]}"#;
+        assert_eq!(whisper(chat, 0), None);
+        assert_eq!(whisper(chat, 1), None);
+        // a chat plugin that sends the message without the sender's name
+        assert_eq!(whisper(r#"{"text":"[Owner -> me] !quit"}"#, 0), None);
+        let json = r#"{"translate":"commands.message.display.incoming","with":["Owner","!quit"]}"#;
+        assert_eq!(whisper(json, 0), None);
+    }
+}
@@ -0,0 +1,267 @@
+// running without the window, e.g. as a service on a server without a display
+// the settings come from a profile in the config file, the log is printed or only written to a file
+
+use mc_afk_bot::api::{self, Connect};
+use mc_afk_bot::config;
+use mc_afk_bot::logger::Logger;
+use mc_afk_bot::manager::{Manager, Session};
+use mc_afk_bot::schedule;
+use mc_afk_bot::webhooks::Webhooks;
+use mc_afk_bot::{clone_all, clone_mut};
+use mc_afk_bot::{Bot, Disconnect, Event, Status};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+use tokio::stream::StreamExt;
+
+// exit codes, so that service managers and scripts can tell what happened
+const EXIT_REQUESTED: i32 = 0; // stopped with Ctrl+C, SIGTERM or by an owner with /msg
+const EXIT_ERROR: i32 = 1; // couldn't connect, lost the connection or got kicked
+const EXIT_USAGE: i32 = 2; // bad arguments or config
+const EXIT_LOW_HEALTH: i32 = 3; // disconnected to avoid dying
+
+const USAGE: &str = "\
+Usage: mc_afk_bot [options]
+
+Runs the bot without the window, with the settings of a profile from the config file.
+The profiles are made and saved in the window.
+
+Options:
+  --config <file>       the config file, mc_afk_bot.toml next to the executable by default
+  --profile <name>      the profile to use, the one that was used last by default
+  --address <address>   the server to connect to, instead of the one in the profile
+  --username <name>     the username to use, instead of the one in the profile
+  --log <file>          write the log to this file instead of printing it and writing it to
+                        the one from the config file
+  --headless            run without the window even when no other options are given
+  --help                show this message
+
+Exit codes:
+  0  disconnected on request (Ctrl+C, SIGTERM or /msg quit)
+  1  couldn't connect, lost the connection or got kicked
+  2  bad arguments or config
+  3  disconnected because the health was too low";
+
+#[derive(Debug, Default)]
+struct Options {
+    config: Option<PathBuf>,
+    profile: Option<String>,
+    address: Option<String>,
+    username: Option<String>,
+    log: Option<PathBuf>,
+}
+
+// runs the bot until it disconnects, returns the exit code
+pub fn run(runtime: &Runtime, args: Vec<String>) -> i32 {
+    let options = match parse_args(args) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return EXIT_REQUESTED;
+        }
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return EXIT_USAGE;
+        }
+    };
+
+    let config = match &options.config {
+        Some(path) => config::load_from(path),
+        None => config::load(),
+    };
+
+    // the defaults are used when the config can't be read, so that the error still gets logged
+    let log_settings = match &config {
+        Ok(config) => config.log.clone(),
+        Err(_) => Default::default(),
+    };
+    let log_file = options.log.clone().or_else(|| log_settings.path());
+    let (_, errors) = Logger::init(&log_settings, log_file, options.log.is_none());
+    for e in errors {
+        // with --log nothing is printed, so the log would be lost
+        if options.log.is_some() {
+            eprintln!("{}", e);
+            return EXIT_USAGE;
+        }
+        log::warn!("{}", e);
+    }
+    let write_to_log = |message: String| log::info!("{}", message);
+
+    let config = match config {
+        Ok(config) => config,
+        Err(e) => {
+            log::error!("{}", e);
+            return EXIT_USAGE;
+        }
+    };
+
+    let api_settings = config.api.clone();
+    let webhook_settings = config.webhooks.clone();
+    let name = options.profile.unwrap_or(config.current_profile);
+    let profile = match config.profiles.get(&name) {
+        Some(profile) => profile.clone(),
+        // running with only --address and --username is fine without any profiles
+        None if name.is_empty() => Default::default(),
+        None => {
+            log::error!("There's no profile {:?}", name);
+            return EXIT_USAGE;
+        }
+    };
+    let address = options.address.unwrap_or(profile.address);
+    let username = options.username.unwrap_or(profile.username);
+    if address.is_empty() || username.is_empty() {
+        log::error!(
+            "Please provide a server address and a username, in the profile or with --address and --username"
+        );
+        return EXIT_USAGE;
+    }
+
+    let mut settings = profile.settings;
+    let (scheduled_messages, errors) = schedule::parse(&settings.schedule);
+    for e in errors {
+        log::warn!("Schedule: {}", e);
+    }
+    settings.scheduled_messages = scheduled_messages;
+    let settings = Arc::new(tokio::sync::Mutex::new(settings));
+
+    runtime.block_on(async move {
+        write_to_log(format!("Connecting to {:?} as {:?}.", address, username));
+        let (bot, mut events) = Bot::connect(address.clone(), username.clone(), settings);
+        let manager = Arc::new(std::sync::Mutex::new(Manager::default()));
+        let id = manager.lock().unwrap().add(Session {
+            address,
+            username,
+            bot: bot.clone(),
+            status: None,
+            statistics: Vec::new(),
+        });
+        let mut webhooks_task = None;
+        if !webhook_settings.url.is_empty() {
+            match Webhooks::new(webhook_settings, write_to_log) {
+                Ok((webhooks, task)) => {
+                    manager.lock().unwrap().set_webhooks(Some(webhooks));
+                    webhooks_task = Some(tokio::spawn(task));
+                }
+                Err(e) => log::warn!("{}", e),
+            }
+        }
+        if api_settings.enabled {
+            let connect: Connect = Arc::new(|_| {
+                Box::pin(async {
+                    Err(
+                        "Only one bot runs in headless mode, start another process for more"
+                            .to_string(),
+                    )
+                })
+            });
+            tokio::spawn(api::serve(
+                api_settings,
+                manager.clone(),
+                connect,
+                write_to_log,
+            ));
+        }
+
+        // disconnect cleanly when asked to stop
+        tokio::spawn({
+            clone_mut![write_to_log];
+            clone_all![bot];
+            async move {
+                let signal = wait_for_signal().await;
+                write_to_log(format!("Got {}, disconnecting.", signal));
+                bot.disconnect().await;
+            }
+        });
+
+        let mut status = Status::default();
+        let mut logged_status = None;
+        // the statistics are sent every second, so only the last ones are shown when disconnecting
+        let mut statistics = String::new();
+        let mut disconnect = Disconnect::Error;
+        while let Some(event) = events.next().await {
+            manager.lock().unwrap().publish(id, &event);
+            match event {
+                Event::Log(level, message) => log::log!(level, "{}", message),
+                Event::StatisticsUpdated { rows, text } => {
+                    manager.lock().unwrap().set_statistics(id, rows);
+                    statistics = text;
+                }
+                Event::Disconnected(reason) => disconnect = reason,
+                // only log the status when it changes, health updates often change nothing
+                event => {
+                    if !status.update(&event) {
+                        continue;
+                    }
+                    manager.lock().unwrap().set_status(id, status);
+                    if logged_status != Some(status) {
+                        write_to_log(format!("Status: {}", status));
+                        logged_status = Some(status);
+                    }
+                }
+            }
+        }
+
+        if !statistics.is_empty() {
+            write_to_log(format!("Statistics since connecting:\n{}", statistics));
+        }
+        write_to_log("Disconnected.".to_string());
+        // the last events are usually why the bot stopped, so they're worth waiting for
+        if let Some(webhooks_task) = webhooks_task {
+            manager.lock().unwrap().set_webhooks(None);
+            let _ = tokio::time::timeout(Duration::from_secs(30), webhooks_task).await;
+        }
+        match disconnect {
+            Disconnect::Requested => EXIT_REQUESTED,
+            Disconnect::LowHealth => EXIT_LOW_HEALTH,
+            Disconnect::Error => EXIT_ERROR,
+        }
+    })
+}
+
+// returns None if only the usage was asked for
+fn parse_args(args: Vec<String>) -> Result<Option<Options>, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing the value for {}", arg))
+        };
+        match arg.as_str() {
+            "--config" => options.config = Some(PathBuf::from(value()?)),
+            "--profile" => options.profile = Some(value()?),
+            "--address" => options.address = Some(value()?),
+            "--username" => options.username = Some(value()?),
+            "--log" => options.log = Some(PathBuf::from(value()?)),
+            "--headless" => {}
+            "--help" | "-h" => return Ok(None),
+            _ => return Err(format!("Unknown option {:?}", arg)),
+        }
+    }
+
+    Ok(Some(options))
+}
+
+#[cfg(unix)]
+async fn wait_for_signal() -> &'static str {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    // systemd stops services with SIGTERM
+    match signal(SignalKind::terminate()) {
+        Ok(mut terminate) => tokio::select! {
+            _ = tokio::signal::ctrl_c() => "Ctrl+C",
+            _ = terminate.recv() => "SIGTERM",
+        },
+        Err(_) => {
+            let _ = tokio::signal::ctrl_c().await;
+            "Ctrl+C"
+        }
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_signal() -> &'static str {
+    let _ = tokio::signal::ctrl_c().await;
+    "Ctrl+C"
+}
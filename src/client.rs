@@ -1,8 +1,10 @@
 use crate::asyncio::{AsyncVec, SizeCalc};
 use crate::datatypes::*;
+use crate::metrics::Metrics;
 use crate::packets::{ClientBound, ServerBound};
 use flate2::read::ZlibDecoder;
 use flate2::write::ZlibEncoder;
+use std::sync::Arc;
 use tokio::io::{self, AsyncReadExt};
 use tokio::net::TcpStream;
 use tokio::prelude::*;
@@ -11,6 +13,7 @@ pub struct Client {
     pub stream: TcpStream,
     pub status: i64, // 0 - handshake, 1 - status, 2 - login, 3 - play
     pub compression: i64,
+    pub metrics: Arc<Metrics>,
 }
 
 impl Client {
@@ -22,6 +25,7 @@ impl Client {
 
         let mut size = SizeCalc(0);
         packet.clone().gen_to(&mut size).await?;
+        self.metrics.sent(&packet, size.0);
 
         if self.compression > 0 {
             // packet is sent in the compressed format
@@ -63,6 +67,8 @@ impl Client {
     }
     pub async fn receive(&mut self) -> io::Result<ClientBound> {
         let packet_length = VarInt::deserialize(&mut self.stream).await?.0;
+        // the size of the packet id and data, before compressing
+        let mut size = packet_length;
 
         let packet = if self.compression > 0 {
             let uncompressed_data_size = VarInt::deserialize(&mut self.stream).await?;
@@ -79,15 +85,16 @@ impl Client {
                 self.stream.read_exact(&mut bytes).await?;
 
                 let mut decoder = ZlibDecoder::new(std::io::Cursor::new(bytes));
+                size = uncompressed_data_size.0;
 
-                Ok(ClientBound::read_from(
-                    &mut decoder,
-                    packet_length - uncompressed_data_size.size() as i64,
-                    self.status,
+                // the uncompressed data size is the length of the packet id and data
+                Ok(
+                    ClientBound::read_from(&mut decoder, uncompressed_data_size.0, self.status)
+                        .await?,
                 )
-                .await?)
             } else {
                 // the data is not compressed
+                size = packet_length - uncompressed_data_size.size() as i64;
                 Ok(ClientBound::read_from(
                     &mut self.stream,
                     packet_length - uncompressed_data_size.size() as i64,
@@ -100,6 +107,10 @@ impl Client {
             Ok(ClientBound::read_from(&mut self.stream, packet_length, self.status).await?)
         };
 
+        if let Ok(packet) = &packet {
+            self.metrics.received(packet, size as u64);
+        }
+
         // change the status as needed
         if let Ok(ClientBound::LoginSuccess(..)) = &packet {
             self.status = 3;
@@ -0,0 +1,65 @@
+// the settings saved between launches, as named profiles for different farms
+
+use crate::api::ApiSettings;
+use crate::logger::LogSettings;
+use crate::webhooks::WebhookSettings;
+use crate::Settings;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const FILE_NAME: &str = "mc_afk_bot.toml";
+// used when saving without ever having chosen a profile
+pub const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub current_profile: String, // the one that was used last, restored on launch
+    pub profiles: BTreeMap<String, Profile>,
+    pub api: ApiSettings, // for all the bots, so it's not part of the profiles
+    pub webhooks: WebhookSettings,
+    pub log: LogSettings,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub address: String,
+    pub username: String,
+    pub settings: Settings,
+}
+
+// next to the executable, so that it's easy to find and back up
+pub fn path() -> PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(FILE_NAME)))
+        .unwrap_or_else(|| PathBuf::from(FILE_NAME))
+}
+
+pub fn load() -> Result<Config, String> {
+    let path = path();
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+
+    load_from(&path)
+}
+
+// unlike load, a missing file is an error, because it was asked for explicitly
+pub fn load_from(path: &Path) -> Result<Config, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("Couldn't read {}: {}", path.display(), e))?;
+    toml::from_str(&text).map_err(|e| format!("Couldn't parse {}: {}", path.display(), e))
+}
+
+pub fn save(config: &Config) -> Result<(), String> {
+    let path = path();
+    // going through a Value puts the tables after the plain values, as TOML requires
+    let text = toml::Value::try_from(config)
+        .and_then(|value| toml::to_string_pretty(&value))
+        .map_err(|e| format!("Couldn't serialize the settings: {}", e))?;
+
+    std::fs::write(&path, text).map_err(|e| format!("Couldn't write {}: {}", path.display(), e))
+}
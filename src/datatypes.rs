@@ -1,5 +1,7 @@
 use crate::{MyAsyncRead, MyAsyncWrite};
 use async_trait::async_trait;
+use std::future::Future;
+use std::pin::Pin;
 use tokio::io;
 
 // A data type that is used in the minecraft protocol
@@ -40,6 +42,94 @@ impl VarInt {
     }
 }
 
+// reads and discards named NBT data (https://wiki.vg/NBT), since we don't need any of it
+pub async fn skip_nbt<S: MyAsyncRead + 'static + Send>(input: &mut S) -> io::Result<()> {
+    let tag = u8::deserialize(input).await?;
+    // TAG_End means there is no NBT data at all
+    if tag != 0 {
+        // the name
+        let name_length = u16::deserialize(input).await?;
+        let mut name = vec![0u8; name_length as usize];
+        input.read(&mut name).await?;
+
+        skip_nbt_payload(input, tag).await?;
+    }
+
+    Ok(())
+}
+
+// compounds and lists can contain more compounds and lists, so this has to be boxed to recurse
+fn skip_nbt_payload<'a, S: MyAsyncRead + 'static + Send>(
+    input: &'a mut S,
+    tag: u8,
+) -> Pin<Box<dyn Future<Output = io::Result<()>> + Send + 'a>> {
+    Box::pin(async move {
+        let fixed_size = match tag {
+            1 => 1,     // byte
+            2 => 2,     // short
+            3 | 5 => 4, // int, float
+            4 | 6 => 8, // long, double
+            _ => 0,
+        };
+        if fixed_size > 0 {
+            let mut garbage = vec![0u8; fixed_size];
+            input.read(&mut garbage).await?;
+
+            return Ok(());
+        }
+
+        match tag {
+            7 | 11 | 12 => {
+                // byte, int and long arrays
+                let length = i32::deserialize(input).await?.max(0) as usize;
+                let element_size = match tag {
+                    7 => 1,
+                    11 => 4,
+                    _ => 8,
+                };
+                let mut garbage = vec![0u8; length * element_size];
+                input.read(&mut garbage).await?;
+            }
+            8 => {
+                // string
+                let length = u16::deserialize(input).await?;
+                let mut garbage = vec![0u8; length as usize];
+                input.read(&mut garbage).await?;
+            }
+            9 => {
+                // list
+                let element_tag = u8::deserialize(input).await?;
+                let length = i32::deserialize(input).await?;
+                for _ in 0..length {
+                    skip_nbt_payload(input, element_tag).await?;
+                }
+            }
+            10 => {
+                // compound, a list of named tags ending with TAG_End
+                loop {
+                    let tag = u8::deserialize(input).await?;
+                    if tag == 0 {
+                        break;
+                    }
+                    let name_length = u16::deserialize(input).await?;
+                    let mut name = vec![0u8; name_length as usize];
+                    input.read(&mut name).await?;
+
+                    skip_nbt_payload(input, tag).await?;
+                }
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Invalid NBT tag {}", tag),
+                ))
+            }
+        }
+
+        Ok(())
+    })
+}
+
 // DataType implementations //
 //////////////////////////////
 
@@ -0,0 +1,72 @@
+// appends the session statistics to a file, so that farm output can be compared across days
+// .jsonl files get one JSON object per line (JSON Lines), so that rows can be appended like CSV,
+// anything else is written as CSV
+
+use chrono::Local;
+use serde::Serialize;
+use std::path::Path;
+use tokio::fs::OpenOptions;
+use tokio::io::{self, AsyncWriteExt};
+
+// how often the statistics are written, they're also written when disconnecting
+pub const INTERVAL: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Row {
+    pub category: String,
+    pub statistic: String,
+    pub count: i64, // since connecting
+}
+
+pub async fn append(path: &str, server: &str, rows: &[Row]) -> io::Result<()> {
+    let time = Local::now().format("%F %T").to_string();
+    let json = path.to_lowercase().ends_with(".jsonl");
+    let new_file = !Path::new(path).exists();
+
+    let mut text = String::new();
+    if json {
+        for row in rows {
+            text += &serde_json::json!({
+                "time": time,
+                "server": server,
+                "category": row.category,
+                "statistic": row.statistic,
+                "count": row.count,
+            })
+            .to_string();
+            text += "\n";
+        }
+    } else {
+        if new_file {
+            text += "time,server,category,statistic,count\n";
+        }
+        for row in rows {
+            text += &format!(
+                "{},{},{},{},{}\n",
+                time,
+                csv_field(server),
+                csv_field(&row.category),
+                csv_field(&row.statistic),
+                row.count
+            );
+        }
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    file.write_all(text.as_bytes()).await?;
+
+    Ok(())
+}
+
+// quotes the field if needed
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,1249 @@
+// the window, used unless the bot is started with command-line arguments
+
+use fltk::{
+    app::App, browser::HoldBrowser, button::Button, button::CheckButton, enums::Color,
+    frame::Frame, group::Group, group::Scroll, group::Tabs, input::Input, input::IntInput,
+    input::MultilineInput, input::SecretInput, menu::Choice, prelude::DisplayExt, prelude::MenuExt,
+    prelude::ValuatorExt, text::TextBuffer, text::TextDisplay, text::WrapMode,
+    valuator::HorNiceSlider, window::Window, BrowserExt, GroupExt, InputExt, WidgetBase, WidgetExt,
+};
+use mc_afk_bot::api::{self, Connect, ConnectRequest};
+use mc_afk_bot::behaviors;
+use mc_afk_bot::config::{self, Config, Profile};
+use mc_afk_bot::foods::FOODS;
+use mc_afk_bot::logger::Logger;
+use mc_afk_bot::manager::{Manager, Session};
+use mc_afk_bot::mobs::MOBS;
+use mc_afk_bot::schedule;
+use mc_afk_bot::webhooks::Webhooks;
+use mc_afk_bot::{clone_all, clone_mut};
+use mc_afk_bot::{Bot, Event, Events, Level, Settings, Status};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+use tokio::stream::StreamExt;
+use tokio::sync::Mutex;
+
+// older lines are removed from the log widget, they're still in the log file
+const MAX_LOG_LINES: usize = 1000;
+
+pub fn run(runtime: &Runtime) {
+    let handle = runtime.handle();
+
+    // restore the profile that was used last time
+    let (config, config_error) = match config::load() {
+        Ok(config) => (config, None),
+        Err(e) => (Config::default(), Some(e)),
+    };
+    let profile = config
+        .profiles
+        .get(&config.current_profile)
+        .cloned()
+        .unwrap_or_default();
+    let config = Arc::new(Mutex::new(config));
+
+    // initialize the settings structure, filled in from the profile once the widgets exist
+    let settings = Arc::new(Mutex::new(Settings::default()));
+
+    let app = App::default().with_scheme(fltk::app::Scheme::Gtk);
+
+    let mut window = Window::new(100, 100, 400, 600, "Minecraft AFK Bot");
+
+    let tabs = Tabs::new(0, 0, 400, 600, "");
+
+    let main_tab = Group::new(0, 25, 400, 570, "Main");
+    let mut profile_choice = Choice::new(120, 45, 145, 30, "Profile");
+    let mut save_profile_button = Button::new(270, 45, 55, 30, "Save");
+    let mut delete_profile_button = Button::new(330, 45, 55, 30, "Delete");
+    let mut address_input = Input::new(120, 80, 265, 30, "Server Adress");
+    let username_input = Input::new(120, 115, 265, 30, "Username");
+    let mut connect_button = Button::new(30, 160, 340, 40, "Connect");
+    let mut short_status = Frame::new(15, 205, 370, 30, "not connected");
+    short_status.set_label_color(Color::Dark3);
+    let mut log = TextDisplay::new(15, 240, 370, 345, "");
+    let mut log_buffer = TextBuffer::default();
+    log.set_buffer(Some(log_buffer.clone()));
+    log.wrap_mode(WrapMode::AtBounds, 0);
+    main_tab.end();
+
+    // everything that's logged is shown in the log widget, which only keeps the last lines
+    let log_settings = runtime.block_on(async { config.lock().await.log.clone() });
+    let (logger, log_errors) = Logger::init(&log_settings, log_settings.path(), false);
+    logger.listen({
+        let mut line_lengths = VecDeque::new();
+        move |line: &str| {
+            let line = format!("{}\n", line);
+            log_buffer.append(&line);
+            line_lengths.push_back(line.len() as u32);
+            if line_lengths.len() > MAX_LOG_LINES {
+                let oldest = line_lengths.pop_front().unwrap();
+                log_buffer.remove(0, oldest);
+            }
+            // keep the newest line in sight
+            let lines = log.count_lines(0, log_buffer.length(), true);
+            log.scroll(lines, 0);
+        }
+    });
+
+    // this is a convenience closure for easy writing to the log
+    let write_to_log = |message: String| log::info!("{}", message);
+
+    // this is a convenience closure for easy status updates
+    let change_status = move |message: String, color: Color| {
+        short_status.set_label(&message);
+        short_status.set_label_color(color);
+    };
+
+    let settings_tab = Scroll::new(0, 25, 400, 570, "Settings");
+    let mut sneak_checkbox = CheckButton::new(15, 45, 170, 25, "Sneak");
+    let mut anti_afk_checkbox = CheckButton::new(185, 45, 190, 25, "Anti-AFK movement");
+    let mut min_hp_input = IntInput::new(250, 80, 120, 25, "Disconnect when HP below: ");
+    min_hp_input.set_value("6");
+    min_hp_input.set_maximum_size(2);
+    Frame::new(15, 110, 360, 20, "2 HP = 1 HEART").set_label_color(Color::Dark3);
+    let mut eat_at_input = IntInput::new(250, 140, 120, 25, "Eat when hunger below: ");
+    eat_at_input.set_value("10");
+    min_hp_input.set_maximum_size(2);
+    Frame::new(15, 175, 360, 20, "20 HUNGER = FULL BAR").set_label_color(Color::Dark3);
+    Frame::new(15, 220, 360, 20, "Attack Speed");
+    let mut attack_speed_slider = HorNiceSlider::new(15, 240, 360, 30, "0.0 attacks/second");
+    attack_speed_slider.set_bounds(0.0, 4.472); // the upper bound is sqrt(20)
+    attack_speed_slider.set_precision(1);
+    Frame::new(15, 310, 360, 20, "Weapon");
+    let mut weapon_slider = HorNiceSlider::new(15, 330, 360, 30, "1");
+    weapon_slider.set_bounds(1.0, 9.0);
+    weapon_slider.set_precision(0);
+    Frame::new(15, 380, 360, 20, "choose a hotbar slot 1-9").set_label_color(Color::Dark3);
+    let mut password_input = SecretInput::new(150, 420, 220, 25, "Login password: ");
+    Frame::new(15, 450, 360, 40, "used for /login and /register on servers\nwith auth plugins, saved for the current address").set_label_color(Color::Dark3);
+    let mut owners_input = Input::new(150, 500, 220, 25, "Owners: ");
+    Frame::new(
+        15,
+        530,
+        360,
+        40,
+        "players allowed to control the bot with /msg,\nseparated by commas",
+    )
+    .set_label_color(Color::Dark3);
+    let mut auto_respawn_checkbox =
+        CheckButton::new(15, 580, 360, 25, "Respawn automatically after dying");
+    let mut line_of_sight_checkbox = CheckButton::new(
+        15,
+        610,
+        360,
+        25,
+        "Only attack mobs in line of sight on this server",
+    );
+    let mut return_to_spot_checkbox = CheckButton::new(
+        15,
+        640,
+        360,
+        25,
+        "Walk back to the AFK spot when knocked away",
+    );
+    return_to_spot_checkbox.set_checked(true);
+    let mut goto_input = Input::new(150, 680, 150, 25, "Go to: ");
+    let mut goto_button = Button::new(305, 680, 65, 25, "Go");
+    Frame::new(15, 705, 360, 20, "x y z of the block to stand in").set_label_color(Color::Dark3);
+    let mut goto_on_join_checkbox = CheckButton::new(15, 730, 360, 25, "Go there after joining");
+    let mut statistics_file_input = Input::new(150, 770, 220, 25, "Export statistics: ");
+    Frame::new(
+        15,
+        800,
+        360,
+        40,
+        "a .csv or .jsonl (JSON Lines) file, written every\n5 minutes and when disconnecting",
+    )
+    .set_label_color(Color::Dark3);
+    Frame::new(15, 860, 360, 20, "Behaviors");
+    let mut behavior_checkboxes = Vec::new();
+    let mut i = 0;
+    for (name, description) in behaviors::BUILT_IN.iter() {
+        let mut checkbox = CheckButton::new(15, 885 + i * 25, 360, 25, description);
+        behavior_checkboxes.push(checkbox.clone());
+
+        checkbox.set_callback2({
+            clone_all![handle, settings, write_to_log];
+            move |checkbox| {
+                handle.spawn({
+                    clone_mut![write_to_log];
+                    clone_all![settings, checkbox];
+                    async move {
+                        let is_checked = checkbox.is_checked();
+                        let mut settings_lock = settings.lock().await;
+                        settings_lock.behaviors.retain(|x| x != name);
+                        if is_checked {
+                            settings_lock.behaviors.push(name.to_string());
+                        }
+
+                        write_to_log(format!(
+                            "Behavior {} {}.",
+                            name,
+                            if is_checked {
+                                "turned on"
+                            } else {
+                                "turned off"
+                            }
+                        ));
+                    }
+                });
+            }
+        });
+
+        i += 1;
+    }
+    Frame::new(
+        15,
+        885 + i * 25,
+        360,
+        20,
+        "changes take effect the next time the bot connects",
+    )
+    .set_label_color(Color::Dark3);
+    let mut scripts_folder_input = Input::new(150, 1000, 220, 25, "Scripts folder: ");
+    Frame::new(
+        15,
+        1030,
+        360,
+        40,
+        "a folder of .rhai farm scripts, reloaded when\nthey change, used the next time the bot connects",
+    )
+    .set_label_color(Color::Dark3);
+    settings_tab.end();
+
+    let mobs_tab = Scroll::new(0, 25, 400, 570, "Mobs");
+    Frame::new(15, 40, 360, 20, "select all mobs you wish to attack").set_label_color(Color::Dark3);
+    let mut mob_checkboxes = Vec::new();
+    for (i, mob) in (0..).zip(MOBS.right_values()) {
+        let mut checkbox = CheckButton::new(15 + (i % 2) * 170, 65 + (i / 2) * 20, 155, 20, mob);
+        mob_checkboxes.push(checkbox.clone());
+
+        checkbox.set_callback2({
+            clone_all![handle, settings, write_to_log];
+            move |checkbox| {
+                handle.spawn({
+                    clone_mut![write_to_log];
+                    clone_all![settings, checkbox];
+                    async move {
+                        let is_checked = checkbox.is_checked();
+                        let name = checkbox.label();
+                        let id = *MOBS.get_by_right(&name.as_str()).unwrap();
+                        if is_checked {
+                            settings.lock().await.attack_mobs.push(id);
+                        } else {
+                            settings.lock().await.attack_mobs.retain(|&x| x != id);
+                        }
+
+                        write_to_log(format!(
+                            "Mob {} {}.",
+                            name,
+                            if is_checked { "selected" } else { "unselected" }
+                        ));
+                    }
+                });
+            }
+        });
+    }
+    mobs_tab.end();
+
+    let food_tab = Scroll::new(0, 25, 400, 570, "Food");
+    Frame::new(15, 40, 360, 60, "select all food you wish to automatically eat\nwhen hungry. The bot can only eat food\nthat's in the hotbar, so make sure to have some.").set_label_color(Color::Dark3);
+    let mut food_checkboxes = Vec::new();
+    for (i, food) in (0..).zip(FOODS.right_values()) {
+        let mut checkbox = CheckButton::new(15 + (i % 2) * 170, 110 + (i / 2) * 20, 155, 20, food);
+        food_checkboxes.push(checkbox.clone());
+
+        checkbox.set_callback2({
+            clone_all![handle, settings, write_to_log];
+            move |checkbox| {
+                handle.spawn({
+                    clone_mut![write_to_log];
+                    clone_all![settings, checkbox];
+                    async move {
+                        let is_checked = checkbox.is_checked();
+                        let name = checkbox.label();
+                        let id = *FOODS.get_by_right(&name.as_str()).unwrap();
+                        if is_checked {
+                            settings.lock().await.eat_food.push(id);
+                        } else {
+                            settings.lock().await.eat_food.retain(|&x| x != id);
+                        }
+
+                        write_to_log(format!(
+                            "Food {} {}.",
+                            name,
+                            if is_checked { "selected" } else { "unselected" }
+                        ));
+                    }
+                });
+            }
+        });
+    }
+    food_tab.end();
+
+    let chat_tab = Group::new(0, 25, 400, 570, "Chat");
+    Frame::new(15, 40, 360, 60, "chat messages or commands to send automatically,\none per line (# starts a comment), for example:\nevery 10m /afk     at 18:30 /warp shop     join /home farm").set_label_color(Color::Dark3);
+    let mut schedule_input = MultilineInput::new(15, 110, 370, 470, "");
+    chat_tab.end();
+
+    let bots_tab = Group::new(0, 25, 400, 570, "Bots");
+    Frame::new(15, 40, 360, 40, "run more bots at the same time, each with the\naddress, username and settings of a saved profile").set_label_color(Color::Dark3);
+    let mut bot_profile_choice = Choice::new(120, 85, 185, 30, "Profile");
+    let mut start_bot_button = Button::new(310, 85, 75, 30, "Start");
+    let mut bot_list = HoldBrowser::new(15, 125, 370, 415, "");
+    bot_list.set_column_char('\t');
+    bot_list.set_column_widths(&[150, 90, 70, 50]);
+    // the first line is the header, so a bot's line is its position + 2
+    bot_list.add("@bBot\t@bStatus\t@bHealth\t@bKills");
+    let mut stop_bot_button = Button::new(15, 550, 370, 30, "Stop");
+    bots_tab.end();
+
+    let statistics_tab = Scroll::new(0, 40, 400, 570, "Statistics");
+    let mut statistics_frame = Frame::new(15, 40, 370, 530, "");
+
+    // this is a convenience closure for easy statistics updates
+    let mut update_statistics = move |statistics: String| {
+        statistics_frame.resize(15, 40, 370, 20 * (statistics.lines().count() as i32 + 1));
+        statistics_frame.set_label(&statistics);
+    };
+    update_statistics("Nothing to show yet.".to_string());
+
+    statistics_tab.end();
+
+    tabs.end();
+
+    window.end();
+
+    window.show();
+
+    // shows the profile in all the widgets, and returns its settings, ready to be used
+    let mut apply_profile = {
+        clone_mut![write_to_log, attack_speed_slider, weapon_slider];
+        clone_all![
+            address_input,
+            username_input,
+            sneak_checkbox,
+            anti_afk_checkbox,
+            min_hp_input,
+            eat_at_input,
+            password_input,
+            owners_input,
+            auto_respawn_checkbox,
+            line_of_sight_checkbox,
+            return_to_spot_checkbox,
+            goto_input,
+            goto_on_join_checkbox,
+            statistics_file_input,
+            scripts_folder_input,
+            schedule_input,
+            mob_checkboxes,
+            food_checkboxes,
+            behavior_checkboxes
+        ];
+        move |profile: &Profile| -> Settings {
+            let mut settings = profile.settings.clone();
+            let (scheduled_messages, errors) = schedule::parse(&settings.schedule);
+            for e in errors {
+                write_to_log(format!("Schedule: {}", e));
+            }
+            settings.scheduled_messages = scheduled_messages;
+
+            address_input.set_value(&profile.address);
+            username_input.set_value(&profile.username);
+            sneak_checkbox.set_checked(settings.sneak);
+            anti_afk_checkbox.set_checked(settings.anti_afk);
+            min_hp_input.set_value(&settings.min_health.to_string());
+            eat_at_input.set_value(&settings.eat_at.to_string());
+            // the slider is non-linear, see its callback
+            attack_speed_slider.set_value((settings.attack_speed as f64).sqrt());
+            attack_speed_slider
+                .set_label(&format!("  {:.1} attacks/second  ", settings.attack_speed));
+            weapon_slider.set_value(settings.weapon as f64 + 1.0);
+            weapon_slider.set_label(&format!("  {:}  ", settings.weapon + 1));
+            password_input.set_value(
+                settings
+                    .login_passwords
+                    .get(&profile.address)
+                    .map(|password| password.as_str())
+                    .unwrap_or(""),
+            );
+            owners_input.set_value(&settings.owners.join(", "));
+            auto_respawn_checkbox.set_checked(settings.auto_respawn);
+            line_of_sight_checkbox
+                .set_checked(settings.line_of_sight_servers.contains(&profile.address));
+            return_to_spot_checkbox.set_checked(settings.return_to_spot);
+            goto_input.set_value(&match settings.goto {
+                Some((x, y, z)) => format!("{} {} {}", x, y, z),
+                None => String::new(),
+            });
+            goto_on_join_checkbox.set_checked(settings.goto_on_join);
+            statistics_file_input.set_value(&settings.statistics_file);
+            scripts_folder_input.set_value(&settings.scripts_folder);
+            schedule_input.set_value(&settings.schedule);
+            for checkbox in &mob_checkboxes {
+                let id = *MOBS.get_by_right(&checkbox.label().as_str()).unwrap();
+                checkbox.set_checked(settings.attack_mobs.contains(&id));
+            }
+            for checkbox in &food_checkboxes {
+                let id = *FOODS.get_by_right(&checkbox.label().as_str()).unwrap();
+                checkbox.set_checked(settings.eat_food.contains(&id));
+            }
+            for (checkbox, (name, _)) in behavior_checkboxes.iter().zip(behaviors::BUILT_IN.iter())
+            {
+                checkbox.set_checked(settings.behaviors.iter().any(|x| x == name));
+            }
+
+            settings
+        }
+    };
+    runtime.block_on(async {
+        *settings.lock().await = apply_profile(&profile);
+        let config_lock = config.lock().await;
+        show_profiles(&mut profile_choice, &config_lock);
+        show_profiles(&mut bot_profile_choice, &config_lock);
+    });
+
+    // GUI callback closures follow
+
+    sneak_checkbox.set_callback2({
+        clone_all![handle, settings, write_to_log];
+        move |sneak_checkbox| {
+            handle.spawn({
+                clone_mut![write_to_log];
+                clone_all![settings, sneak_checkbox];
+                async move {
+                    let new = sneak_checkbox.is_checked();
+                    settings.lock().await.sneak = new;
+                    write_to_log(format!("Changed sneak to {}", new));
+                }
+            });
+        }
+    });
+
+    anti_afk_checkbox.set_callback2({
+        clone_all![handle, settings, write_to_log];
+        move |anti_afk_checkbox| {
+            handle.spawn({
+                clone_mut![write_to_log];
+                clone_all![settings, anti_afk_checkbox];
+                async move {
+                    let new = anti_afk_checkbox.is_checked();
+                    settings.lock().await.anti_afk = new;
+                    write_to_log(format!("Changed anti-AFK movement to {}", new));
+                }
+            });
+        }
+    });
+
+    min_hp_input.set_callback2({
+        clone_all![handle, settings, write_to_log];
+        move |min_hp_input| {
+            handle.spawn({
+                clone_mut![write_to_log];
+                clone_all![settings, min_hp_input];
+                async move {
+                    let new = min_hp_input.value();
+                    let new = if new.is_empty() {
+                        0.0
+                    } else {
+                        new.parse().unwrap_or(6.0) // 6 - default
+                    };
+                    settings.lock().await.min_health = new;
+                    write_to_log(format!("Changed min HP to {}", new));
+                }
+            });
+        }
+    });
+
+    eat_at_input.set_callback2({
+        clone_all![handle, settings, write_to_log];
+        move |eat_at_input| {
+            handle.spawn({
+                clone_mut![write_to_log];
+                clone_all![settings, eat_at_input];
+                async move {
+                    let new = eat_at_input.value();
+                    let new = if new.is_empty() {
+                        0.0
+                    } else {
+                        new.parse().unwrap_or(6.0) // 6 - default
+                    };
+                    settings.lock().await.eat_at = new;
+                    write_to_log(format!("Changed minimum hunger to {}", new));
+                }
+            });
+        }
+    });
+
+    attack_speed_slider.set_callback2({
+        clone_all![handle, settings];
+        move |attack_speed_slider| {
+            handle.spawn({
+                clone_mut![attack_speed_slider];
+                clone_all![settings];
+                async move {
+                    let new = attack_speed_slider.value();
+                    // make it non-linear
+                    let new = (new.powi(2) * 10.0).ceil() / 10.0;
+                    settings.lock().await.attack_speed = new as f32;
+                    attack_speed_slider.set_label(&format!("  {:.1} attacks/second  ", new));
+                }
+            });
+        }
+    });
+
+    weapon_slider.set_callback2({
+        clone_all![handle, settings];
+        move |weapon_slider| {
+            handle.spawn({
+                clone_mut![weapon_slider];
+                clone_all![settings];
+                async move {
+                    let new = weapon_slider.value() as u8;
+                    settings.lock().await.weapon = new - 1;
+                    weapon_slider.set_label(&format!("  {:}  ", new));
+                }
+            });
+        }
+    });
+
+    password_input.set_callback2({
+        clone_all![handle, settings, write_to_log, address_input];
+        move |password_input| {
+            handle.spawn({
+                clone_mut![write_to_log];
+                clone_all![settings, password_input, address_input];
+                async move {
+                    let address = address_input.value();
+                    let password = password_input.value();
+                    if password.is_empty() {
+                        settings.lock().await.login_passwords.remove(&address);
+                    } else {
+                        settings
+                            .lock()
+                            .await
+                            .login_passwords
+                            .insert(address.clone(), password);
+                    }
+                    write_to_log(format!("Changed the login password for {:?}", address));
+                }
+            });
+        }
+    });
+
+    auto_respawn_checkbox.set_callback2({
+        clone_all![handle, settings, write_to_log];
+        move |auto_respawn_checkbox| {
+            handle.spawn({
+                clone_mut![write_to_log];
+                clone_all![settings, auto_respawn_checkbox];
+                async move {
+                    let new = auto_respawn_checkbox.is_checked();
+                    settings.lock().await.auto_respawn = new;
+                    write_to_log(format!("Changed automatic respawning to {}", new));
+                }
+            });
+        }
+    });
+
+    line_of_sight_checkbox.set_callback2({
+        clone_all![handle, settings, write_to_log, address_input];
+        move |line_of_sight_checkbox| {
+            handle.spawn({
+                clone_mut![write_to_log];
+                clone_all![settings, line_of_sight_checkbox, address_input];
+                async move {
+                    let address = address_input.value();
+                    let new = line_of_sight_checkbox.is_checked();
+                    let mut settings_lock = settings.lock().await;
+                    if new {
+                        settings_lock.line_of_sight_servers.insert(address.clone());
+                    } else {
+                        settings_lock.line_of_sight_servers.remove(&address);
+                    }
+                    write_to_log(format!(
+                        "Changed line of sight check for {:?} to {}",
+                        address, new
+                    ));
+                }
+            });
+        }
+    });
+
+    return_to_spot_checkbox.set_callback2({
+        clone_all![handle, settings, write_to_log];
+        move |return_to_spot_checkbox| {
+            handle.spawn({
+                clone_mut![write_to_log];
+                clone_all![settings, return_to_spot_checkbox];
+                async move {
+                    let new = return_to_spot_checkbox.is_checked();
+                    settings.lock().await.return_to_spot = new;
+                    write_to_log(format!("Changed returning to the AFK spot to {}", new));
+                }
+            });
+        }
+    });
+
+    owners_input.set_callback2({
+        clone_all![handle, settings, write_to_log];
+        move |owners_input| {
+            handle.spawn({
+                clone_mut![write_to_log];
+                clone_all![settings, owners_input];
+                async move {
+                    let new: Vec<String> = owners_input
+                        .value()
+                        .split(',')
+                        .map(|owner| owner.trim().to_string())
+                        .filter(|owner| !owner.is_empty())
+                        .collect();
+                    write_to_log(format!("Changed owners to {:?}", new));
+                    settings.lock().await.owners = new;
+                }
+            });
+        }
+    });
+
+    schedule_input.set_callback2({
+        clone_all![handle, settings, write_to_log];
+        move |schedule_input| {
+            handle.spawn({
+                clone_mut![write_to_log];
+                clone_all![settings, schedule_input];
+                async move {
+                    let text = schedule_input.value();
+                    let (new, errors) = schedule::parse(&text);
+                    for e in errors {
+                        write_to_log(format!("Schedule: {}", e));
+                    }
+                    write_to_log(format!("Changed the schedule, {} messages.", new.len()));
+                    let mut settings_lock = settings.lock().await;
+                    settings_lock.schedule = text;
+                    settings_lock.scheduled_messages = new;
+                }
+            });
+        }
+    });
+
+    // show the stored password and line of sight check of the server when the address changes
+    address_input.set_callback2({
+        clone_all![handle, settings, password_input, line_of_sight_checkbox];
+        move |address_input| {
+            handle.spawn({
+                clone_mut![password_input, line_of_sight_checkbox];
+                clone_all![settings, address_input];
+                async move {
+                    let settings_lock = settings.lock().await;
+                    let address = address_input.value();
+                    let password = settings_lock
+                        .login_passwords
+                        .get(&address)
+                        .cloned()
+                        .unwrap_or_default();
+                    password_input.set_value(&password);
+                    line_of_sight_checkbox
+                        .set_checked(settings_lock.line_of_sight_servers.contains(&address));
+                }
+            });
+        }
+    });
+
+    // when connected, holds the bot, for disconnecting it
+    let connected: Arc<Mutex<Option<Bot>>> = Arc::new(Mutex::new(None));
+
+    // all the running bots, including the one connected with the main tab
+    let manager = Arc::new(std::sync::Mutex::new(Manager::default()));
+
+    connect_button.set_callback2({
+        clone_all![
+            handle,
+            settings,
+            connected,
+            manager,
+            update_statistics,
+            write_to_log,
+            change_status,
+            address_input,
+            username_input,
+            bot_list
+        ];
+        move |connect_button| {
+            handle.spawn({
+                clone_mut![
+                    write_to_log,
+                    change_status,
+                    address_input,
+                    username_input,
+                    connect_button,
+                    bot_list
+                ];
+                clone_mut![update_statistics];
+                clone_all![settings, connected, manager];
+                async move {
+                    let mut connected_lock = connected.lock().await;
+                    if let Some(bot) = &*connected_lock {
+                        // disconnect
+                        bot.disconnect().await;
+                    } else {
+                        // connect
+
+                        // make sure an username is provided
+                        // no need to check the address, because it will be checked at some point automatically
+                        if username_input.value().is_empty() {
+                            write_to_log("Please provide a username!".to_string());
+                            return;
+                        }
+                        let started = start_bot(
+                            &mut manager.lock().unwrap(),
+                            &mut bot_list,
+                            address_input.value(),
+                            username_input.value(),
+                            settings,
+                        );
+                        let (id, bot, mut events) = match started {
+                            Ok(started) => started,
+                            Err(e) => {
+                                write_to_log(e);
+                                return;
+                            }
+                        };
+                        address_input.set_readonly(true);
+                        username_input.set_readonly(true);
+                        connect_button.set_label("Disconnect");
+                        *connected_lock = Some(bot);
+                        drop(connected_lock);
+                        write_to_log(format!(
+                            "Connecting to {:?} as {:?}.",
+                            address_input.value(),
+                            username_input.value(),
+                        ));
+                        let mut status = Status::default();
+                        while let Some(event) = events.next().await {
+                            manager.lock().unwrap().publish(id, &event);
+                            match event {
+                                Event::Log(level, message) => log::log!(level, "{}", message),
+                                Event::StatisticsUpdated { rows, text } => {
+                                    manager.lock().unwrap().set_statistics(id, rows);
+                                    update_statistics(text);
+                                }
+                                event => {
+                                    if status.update(&event) {
+                                        change_status(format!("{}", status), Color::DarkGreen);
+                                        update_bot(
+                                            &mut manager.lock().unwrap(),
+                                            &mut bot_list,
+                                            id,
+                                            status,
+                                        );
+                                    }
+                                }
+                            }
+                        }
+                        remove_bot(&mut manager.lock().unwrap(), &mut bot_list, id);
+                        *connected.lock().await = None;
+                        change_status("not connected".to_string(), Color::Dark3);
+                        address_input.set_readonly(false);
+                        username_input.set_readonly(false);
+                        connect_button.set_label("Connect");
+                        write_to_log("Disconnected.".to_string());
+                    }
+                }
+            });
+        }
+    });
+
+    start_bot_button.set_callback2({
+        clone_mut![write_to_log];
+        clone_all![handle, config, manager, bot_profile_choice, bot_list];
+        move |_| {
+            let name = match bot_profile_choice.choice() {
+                Some(name) => name,
+                None => {
+                    write_to_log("Choose a profile to start the bot with.".to_string());
+                    return;
+                }
+            };
+            handle.spawn({
+                clone_mut![write_to_log];
+                clone_all![config, manager, bot_list];
+                async move {
+                    let profile = match config.lock().await.profiles.get(&name) {
+                        Some(profile) => profile.clone(),
+                        None => return,
+                    };
+                    if let Err(e) = start_profile_bot(&name, profile, &manager, &bot_list) {
+                        log::warn!("{}", e);
+                    }
+                }
+            });
+        }
+    });
+
+    let webhook_settings = runtime.block_on(async { config.lock().await.webhooks.clone() });
+    if !webhook_settings.url.is_empty() {
+        match Webhooks::new(webhook_settings, write_to_log) {
+            Ok((webhooks, task)) => {
+                manager.lock().unwrap().set_webhooks(Some(webhooks));
+                handle.spawn(task);
+            }
+            Err(e) => log::warn!("{}", e),
+        }
+    }
+
+    // the API starts bots the same way as the Start button
+    let api_settings = runtime.block_on(async { config.lock().await.api.clone() });
+    if api_settings.enabled {
+        let connect: Connect = Arc::new({
+            clone_all![config, manager, bot_list];
+            move |request: ConnectRequest| {
+                clone_all![config, manager, bot_list];
+                Box::pin(async move {
+                    let (name, profile) = request.resolve(&*config.lock().await)?;
+                    start_profile_bot(&name, profile, &manager, &bot_list)
+                })
+            }
+        });
+        handle.spawn(api::serve(
+            api_settings,
+            manager.clone(),
+            connect,
+            write_to_log,
+        ));
+    }
+
+    stop_bot_button.set_callback2({
+        clone_mut![write_to_log];
+        clone_all![handle, manager, bot_list];
+        move |_| {
+            let line = (2..=bot_list.size()).find(|&line| bot_list.selected(line));
+            let bot = line.and_then(|line| {
+                manager
+                    .lock()
+                    .unwrap()
+                    .nth(line as usize - 2)
+                    .map(|session| session.bot.clone())
+            });
+            match bot {
+                Some(bot) => {
+                    handle.spawn(async move { bot.disconnect().await });
+                }
+                None => write_to_log("Select a bot to stop.".to_string()),
+            }
+        }
+    });
+
+    goto_input.set_callback2({
+        clone_all![handle, settings, write_to_log];
+        move |goto_input| {
+            handle.spawn({
+                clone_mut![write_to_log];
+                clone_all![settings, goto_input];
+                async move {
+                    let coordinates: Vec<Option<i32>> = goto_input
+                        .value()
+                        .split_whitespace()
+                        .map(|number| number.parse().ok())
+                        .collect();
+                    let new = match coordinates[..] {
+                        [Some(x), Some(y), Some(z)] => Some((x, y, z)),
+                        [] => None,
+                        _ => {
+                            write_to_log("Invalid coordinates, expected x y z".to_string());
+                            None
+                        }
+                    };
+                    write_to_log(format!("Changed the destination to {:?}", new));
+                    settings.lock().await.goto = new;
+                }
+            });
+        }
+    });
+
+    goto_button.set_callback2({
+        clone_all![handle, settings, write_to_log, connected];
+        move |_| {
+            handle.spawn({
+                clone_mut![write_to_log];
+                clone_all![settings, connected];
+                async move {
+                    let mut settings_lock = settings.lock().await;
+                    if connected.lock().await.is_none() {
+                        write_to_log("Not connected.".to_string());
+                    } else if settings_lock.goto.is_none() {
+                        write_to_log("Enter the coordinates to go to first.".to_string());
+                    } else {
+                        settings_lock.goto_now = true;
+                    }
+                }
+            });
+        }
+    });
+
+    goto_on_join_checkbox.set_callback2({
+        clone_all![handle, settings, write_to_log];
+        move |goto_on_join_checkbox| {
+            handle.spawn({
+                clone_mut![write_to_log];
+                clone_all![settings, goto_on_join_checkbox];
+                async move {
+                    let new = goto_on_join_checkbox.is_checked();
+                    settings.lock().await.goto_on_join = new;
+                    write_to_log(format!(
+                        "Changed going to the destination after joining to {}",
+                        new
+                    ));
+                }
+            });
+        }
+    });
+
+    statistics_file_input.set_callback2({
+        clone_all![handle, settings, write_to_log];
+        move |statistics_file_input| {
+            handle.spawn({
+                clone_mut![write_to_log];
+                clone_all![settings, statistics_file_input];
+                async move {
+                    let new = statistics_file_input.value().trim().to_string();
+                    write_to_log(format!("Changed the statistics file to {:?}", new));
+                    settings.lock().await.statistics_file = new;
+                }
+            });
+        }
+    });
+
+    scripts_folder_input.set_callback2({
+        clone_all![handle, settings, write_to_log];
+        move |scripts_folder_input| {
+            handle.spawn({
+                clone_mut![write_to_log];
+                clone_all![settings, scripts_folder_input];
+                async move {
+                    let new = scripts_folder_input.value().trim().to_string();
+                    write_to_log(format!("Changed the scripts folder to {:?}", new));
+                    settings.lock().await.scripts_folder = new;
+                }
+            });
+        }
+    });
+
+    profile_choice.set_callback2({
+        clone_all![
+            handle,
+            settings,
+            config,
+            config_error,
+            connected,
+            write_to_log,
+            apply_profile
+        ];
+        move |profile_choice| {
+            let name = match profile_choice.choice() {
+                Some(name) => name,
+                None => return,
+            };
+            handle.spawn({
+                clone_mut![write_to_log, apply_profile, profile_choice];
+                clone_all![settings, config, config_error, connected];
+                async move {
+                    let mut config_lock = config.lock().await;
+                    if connected.lock().await.is_some() {
+                        write_to_log("Disconnect before switching profiles.".to_string());
+                        show_profiles(&mut profile_choice, &config_lock);
+                        return;
+                    }
+                    let profile = match config_lock.profiles.get(&name) {
+                        Some(profile) => profile.clone(),
+                        None => return,
+                    };
+                    *settings.lock().await = apply_profile(&profile);
+                    config_lock.current_profile = name.clone();
+                    if let Err(e) = save_config(&config_lock, &config_error) {
+                        write_to_log(e);
+                    }
+                    write_to_log(format!("Loaded profile {:?}", name));
+                }
+            });
+        }
+    });
+
+    save_profile_button.set_callback2({
+        clone_all![
+            handle,
+            settings,
+            config,
+            config_error,
+            write_to_log,
+            address_input,
+            username_input,
+            profile_choice,
+            bot_profile_choice,
+            window
+        ];
+        move |_| {
+            // dialogs have to be shown from the GUI thread
+            let current = profile_choice
+                .choice()
+                .unwrap_or_else(|| config::DEFAULT_PROFILE.to_string());
+            let name = match fltk::dialog::input(
+                window.x() + 50,
+                window.y() + 150,
+                "Profile name:",
+                &current,
+            ) {
+                Some(name) => name.trim().to_string(),
+                None => return,
+            };
+            handle.spawn({
+                clone_mut![write_to_log, profile_choice, bot_profile_choice];
+                clone_all![
+                    settings,
+                    config,
+                    config_error,
+                    address_input,
+                    username_input
+                ];
+                async move {
+                    // these would break the list of profiles
+                    if name.is_empty() || name.contains(['/', '|', '\\']) {
+                        write_to_log(format!("Invalid profile name {:?}", name));
+                        return;
+                    }
+                    let profile = Profile {
+                        address: address_input.value(),
+                        username: username_input.value(),
+                        settings: settings.lock().await.clone(),
+                    };
+                    let mut config_lock = config.lock().await;
+                    config_lock.profiles.insert(name.clone(), profile);
+                    config_lock.current_profile = name.clone();
+                    show_profiles(&mut profile_choice, &config_lock);
+                    show_profiles(&mut bot_profile_choice, &config_lock);
+                    match save_config(&config_lock, &config_error) {
+                        Ok(()) => write_to_log(format!("Saved profile {:?}", name)),
+                        Err(e) => write_to_log(e),
+                    }
+                }
+            });
+        }
+    });
+
+    delete_profile_button.set_callback2({
+        clone_all![
+            handle,
+            config,
+            config_error,
+            write_to_log,
+            profile_choice,
+            bot_profile_choice
+        ];
+        move |_| {
+            let name = match profile_choice.choice() {
+                Some(name) => name,
+                None => return,
+            };
+            handle.spawn({
+                clone_mut![write_to_log, profile_choice, bot_profile_choice];
+                clone_all![config, config_error];
+                async move {
+                    let mut config_lock = config.lock().await;
+                    config_lock.profiles.remove(&name);
+                    if config_lock.current_profile == name {
+                        config_lock.current_profile = String::new();
+                    }
+                    show_profiles(&mut profile_choice, &config_lock);
+                    show_profiles(&mut bot_profile_choice, &config_lock);
+                    match save_config(&config_lock, &config_error) {
+                        Ok(()) => write_to_log(format!("Deleted profile {:?}", name)),
+                        Err(e) => write_to_log(e),
+                    }
+                }
+            });
+        }
+    });
+
+    write_to_log("Started Minecraft AFK Bot application.".to_string());
+    for e in config_error.iter().chain(&log_errors) {
+        log::warn!("{}", e);
+    }
+    if let Some(e) = &config_error {
+        fltk::dialog::alert(
+            window.x() + 50,
+            window.y() + 150,
+            &format!("{}\n\nThe settings won't be saved until it's fixed.", e),
+        );
+    }
+    // change_status(format!("Hello"), Color::DarkGreen);
+    app.run().unwrap();
+
+    // remember the settings for the next launch, in the current profile
+    runtime.block_on(async {
+        let mut config_lock = config.lock().await;
+        if config_lock.current_profile.is_empty() {
+            config_lock.current_profile = config::DEFAULT_PROFILE.to_string();
+        }
+        let profile = Profile {
+            address: address_input.value(),
+            username: username_input.value(),
+            settings: settings.lock().await.clone(),
+        };
+        let name = config_lock.current_profile.clone();
+        config_lock.profiles.insert(name, profile);
+        if let Err(e) = save_config(&config_lock, &config_error) {
+            eprintln!("{}", e);
+        }
+    });
+}
+
+// when the config file couldn't be read, saving would replace every profile in it with the defaults
+fn save_config(config: &Config, config_error: &Option<String>) -> Result<(), String> {
+    match config_error {
+        Some(_) => Err(format!(
+            "Not saving the settings, fix {} and restart first.",
+            config::path().display()
+        )),
+        None => config::save(config),
+    }
+}
+
+// fills the list of profiles and selects the current one
+fn show_profiles(profile_choice: &mut Choice, config: &Config) {
+    profile_choice.clear();
+    for name in config.profiles.keys() {
+        profile_choice.add_choice(name);
+    }
+    if let Some(index) = config
+        .profiles
+        .keys()
+        .position(|name| *name == config.current_profile)
+    {
+        profile_choice.set_value(index as i32);
+    }
+}
+
+// connects a bot and adds it to the manager and to the list of bots
+fn start_bot(
+    manager: &mut Manager,
+    bot_list: &mut HoldBrowser,
+    address: String,
+    username: String,
+    settings: Arc<Mutex<Settings>>,
+) -> Result<(u32, Bot, Events), String> {
+    if manager.is_running(&address, &username) {
+        return Err(format!("{} is already connected to {}", username, address));
+    }
+
+    let (bot, events) = Bot::connect(address.clone(), username.clone(), settings);
+    let id = manager.add(Session {
+        address,
+        username,
+        bot: bot.clone(),
+        status: None,
+        statistics: Vec::new(),
+    });
+    bot_list.add(&bot_row(manager.get(id).unwrap()));
+    Ok((id, bot, events))
+}
+
+// starts a bot with the address, username and settings of a profile, returns its id
+fn start_profile_bot(
+    name: &str,
+    profile: Profile,
+    manager: &Arc<std::sync::Mutex<Manager>>,
+    bot_list: &HoldBrowser,
+) -> Result<u32, String> {
+    if profile.address.is_empty() || profile.username.is_empty() {
+        return Err(format!(
+            "Profile {:?} has no address or username, save it with them first.",
+            name
+        ));
+    }
+
+    // the log is shared by all the bots, so their messages start with the username
+    let bot_log = {
+        let username = profile.username.clone();
+        move |level: Level, message: String| log::log!(level, "[{}] {}", username, message)
+    };
+
+    let mut settings = profile.settings;
+    let (scheduled_messages, errors) = schedule::parse(&settings.schedule);
+    for e in errors {
+        bot_log(Level::Warn, format!("Schedule: {}", e));
+    }
+    settings.scheduled_messages = scheduled_messages;
+
+    let (id, _, mut events) = start_bot(
+        &mut manager.lock().unwrap(),
+        &mut bot_list.clone(),
+        profile.address.clone(),
+        profile.username,
+        Arc::new(Mutex::new(settings)),
+    )?;
+    bot_log(
+        Level::Info,
+        format!(
+            "Connecting to {:?} with profile {:?}.",
+            profile.address, name
+        ),
+    );
+
+    tokio::spawn({
+        clone_mut![bot_list];
+        clone_all![manager];
+        async move {
+            let mut status = Status::default();
+            while let Some(event) = events.next().await {
+                manager.lock().unwrap().publish(id, &event);
+                match event {
+                    Event::Log(level, message) => bot_log(level, message),
+                    // the statistics tab is only for the bot of the main tab
+                    Event::StatisticsUpdated { rows, .. } => {
+                        manager.lock().unwrap().set_statistics(id, rows)
+                    }
+                    event => {
+                        if status.update(&event) {
+                            update_bot(&mut manager.lock().unwrap(), &mut bot_list, id, status);
+                        }
+                    }
+                }
+            }
+            remove_bot(&mut manager.lock().unwrap(), &mut bot_list, id);
+            bot_log(Level::Info, "Disconnected.".to_string());
+        }
+    });
+    Ok(id)
+}
+
+fn update_bot(manager: &mut Manager, bot_list: &mut HoldBrowser, id: u32, status: Status) {
+    manager.set_status(id, status);
+    if let (Some(position), Some(session)) = (manager.position(id), manager.get(id)) {
+        bot_list.set_text(position as u32 + 2, &bot_row(session));
+    }
+}
+
+fn remove_bot(manager: &mut Manager, bot_list: &mut HoldBrowser, id: u32) {
+    if let Some(position) = manager.position(id) {
+        bot_list.remove(position as u32 + 2);
+    }
+    manager.remove(id);
+}
+
+// the line of the bot in the list, with the columns separated by tabs
+fn bot_row(session: &Session) -> String {
+    let name = format!("{}@{}", session.username, session.address);
+    match session.status {
+        None => format!("{}\tconnecting", name),
+        Some(status) => format!(
+            "{}\t{}\t{}/20\t{}",
+            name,
+            if status.health <= 0.0 {
+                "dead"
+            } else {
+                "online"
+            },
+            status.health,
+            status.kills
+        ),
+    }
+}
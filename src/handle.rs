@@ -0,0 +1,216 @@
+// the handle for running a bot from other programs: events come out of it, requests go in
+
+use crate::behavior::Behavior;
+use crate::bot::{self, Disconnect, State};
+use crate::clone_all;
+use crate::export::Row;
+use crate::metrics::Metrics;
+use crate::Settings;
+use log::Level;
+use serde::Serialize;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::stream::Stream;
+use tokio::sync::{mpsc, Mutex};
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Log(Level, String), // everything that's worth showing to the user, including what's in the other events
+    HealthChanged {
+        health: f32,
+        food: f32,
+    },
+    MobKilled {
+        mob: String,
+        count: i64, // of this mob since the last statistics update, usually 1
+        total: i64, // of all mobs since connecting
+    },
+    ChatReceived(String), // as plain text
+    Kicked(String),       // the reason, as plain text
+    Died {
+        message: String, // the death message, as plain text
+        pos: (f64, f64, f64),
+    },
+    ItemBroken(String), // the name of a weapon, tool or armor piece that ran out of durability
+    PlayerNearby {
+        name: String,
+        distance: f64, // in blocks
+    },
+    StatisticsUpdated {
+        rows: Vec<Row>, // the statistics that changed since connecting
+        text: String,   // the same, formatted like in the statistics tab
+    },
+    Disconnected(Disconnect), // always the last event
+}
+
+// requests that need the connection, the rest is done by changing the settings
+#[derive(Debug, Clone)]
+pub enum Request {
+    Chat(String), // a message or a command
+    Eat,
+}
+
+#[derive(Debug, Clone)]
+pub struct Bot {
+    settings: Arc<Mutex<Settings>>,
+    state: Arc<Mutex<State>>,
+    metrics: Arc<Metrics>,
+    requests: mpsc::Sender<Request>,
+    shutdown_sender: mpsc::Sender<()>,
+}
+
+impl Bot {
+    // connects in a new task, must be called from within a tokio runtime
+    pub fn connect(
+        address: String,
+        username: String,
+        settings: Arc<Mutex<Settings>>,
+    ) -> (Bot, Events) {
+        Self::connect_with_behaviors(address, username, settings, Vec::new())
+    }
+
+    // the behaviors run along with the built-in ones that are turned on in the settings
+    pub fn connect_with_behaviors(
+        address: String,
+        username: String,
+        settings: Arc<Mutex<Settings>>,
+        behaviors: Vec<Box<dyn Behavior>>,
+    ) -> (Bot, Events) {
+        let (events, receiver) = mpsc::unbounded_channel();
+        let (requests, requests_receiver) = mpsc::channel(16);
+        let (shutdown_sender, shutdown_receiver) = mpsc::channel(1);
+        let state = Arc::new(Mutex::new(State::new()));
+        let metrics = Arc::new(Metrics::default());
+
+        tokio::spawn({
+            clone_all![settings, state, metrics, shutdown_sender];
+            async move {
+                let disconnect = match bot::run(
+                    address,
+                    username,
+                    settings,
+                    state,
+                    metrics,
+                    bot::Channels {
+                        events: events.clone(),
+                        requests: requests_receiver,
+                        shutdown_receiver,
+                        shutdown_sender,
+                    },
+                    behaviors,
+                )
+                .await
+                {
+                    Ok(disconnect) => disconnect,
+                    Err(e) => {
+                        let _ = events.send(Event::Log(Level::Error, format!("Error: {}", e)));
+                        Disconnect::Error
+                    }
+                };
+                let _ = events.send(Event::Disconnected(disconnect));
+            }
+        });
+
+        let bot = Bot {
+            settings,
+            state,
+            metrics,
+            requests,
+            shutdown_sender,
+        };
+        (bot, Events { receiver })
+    }
+
+    // changes to the settings take effect right away
+    pub fn settings(&self) -> &Arc<Mutex<Settings>> {
+        &self.settings
+    }
+
+    // what the bot knows about itself and the world, mostly empty until it joins
+    pub fn state(&self) -> &Arc<Mutex<State>> {
+        &self.state
+    }
+
+    // what's been counted for the metrics
+    pub fn metrics(&self) -> &Arc<Metrics> {
+        &self.metrics
+    }
+
+    pub async fn chat(&self, message: String) {
+        // fails only if the bot has already disconnected
+        let _ = self.requests.send(Request::Chat(message)).await;
+    }
+
+    // eats even if not hungry, if there's food in the hotbar
+    pub async fn eat(&self) {
+        let _ = self.requests.send(Request::Eat).await;
+    }
+
+    // walks to the block, which then becomes the new AFK spot
+    pub async fn goto(&self, block: (i32, i32, i32)) {
+        let mut settings_lock = self.settings.lock().await;
+        settings_lock.goto = Some(block);
+        settings_lock.goto_now = true;
+    }
+
+    pub async fn disconnect(&self) {
+        let _ = self.shutdown_sender.send(()).await;
+    }
+}
+
+// the events of a bot, ending when it disconnects
+pub struct Events {
+    receiver: mpsc::UnboundedReceiver<Event>,
+}
+
+impl Stream for Events {
+    type Item = Event;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Event>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+// the summary of a bot, kept up to date from its events
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Status {
+    pub health: f32,
+    pub food: f32,
+    pub kills: i64, // since connecting
+}
+
+impl Default for Status {
+    fn default() -> Self {
+        Self {
+            health: 20.0,
+            food: 20.0,
+            kills: 0,
+        }
+    }
+}
+
+impl Status {
+    // returns whether the event was about the status, even if nothing changed
+    pub fn update(&mut self, event: &Event) -> bool {
+        match *event {
+            Event::HealthChanged { health, food } => {
+                self.health = health;
+                self.food = food;
+            }
+            Event::MobKilled { total, .. } => self.kills = total,
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "health: {}/20   food: {}/20   kills: {}",
+            self.health, self.food, self.kills
+        )
+    }
+}
@@ -0,0 +1,173 @@
+// just enough HTTP/1.1 for the API: one request per connection, with the body read by its Content-Length
+
+use tokio::io::{self, AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+const MAX_HEAD: usize = 16 * 1024;
+const MAX_BODY: usize = 1024 * 1024;
+
+pub struct Request {
+    pub method: String,
+    pub path: String,                   // without the query
+    pub query: Vec<(String, String)>,   // decoded
+    pub headers: Vec<(String, String)>, // the names in lowercase
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn query(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+pub async fn read_request(stream: &mut TcpStream) -> io::Result<Request> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break end;
+        }
+        if buffer.len() > MAX_HEAD {
+            return Err(invalid("The request head is too long"));
+        }
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "The connection was closed in the middle of the request",
+            ));
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = std::str::from_utf8(&buffer[..head_end])
+        .map_err(|_| invalid("The request head is not valid UTF-8"))?;
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or("").split(' ');
+    let (method, target) = match (request_line.next(), request_line.next()) {
+        (Some(method), Some(target)) if !method.is_empty() => (method.to_string(), target),
+        _ => return Err(invalid("Malformed request line")),
+    };
+    let (path, query) = match target.find('?') {
+        Some(question_mark) => (&target[..question_mark], &target[question_mark + 1..]),
+        None => (target, ""),
+    };
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.find('=') {
+            Some(equals) => (decode(&pair[..equals]), decode(&pair[equals + 1..])),
+            None => (decode(pair), String::new()),
+        })
+        .collect();
+    let mut headers = Vec::new();
+    for line in lines {
+        if let Some(colon) = line.find(':') {
+            let (name, value) = line.split_at(colon);
+            headers.push((name.trim().to_lowercase(), value[1..].trim().to_string()));
+        }
+    }
+
+    let mut request = Request {
+        method,
+        path: path.to_string(),
+        query,
+        headers,
+        body: buffer[head_end + 4..].to_vec(),
+    };
+    let content_length = match request.header("content-length") {
+        Some(length) => length
+            .parse::<usize>()
+            .map_err(|_| invalid("Malformed Content-Length"))?,
+        None => 0,
+    };
+    if content_length > MAX_BODY {
+        return Err(invalid("The request body is too long"));
+    }
+    while request.body.len() < content_length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "The connection was closed in the middle of the request",
+            ));
+        }
+        request.body.extend_from_slice(&chunk[..read]);
+    }
+    request.body.truncate(content_length);
+
+    Ok(request)
+}
+
+pub async fn write_response(stream: &mut TcpStream, status: u16, body: &str) -> io::Result<()> {
+    write_response_with_type(stream, status, "application/json", body).await
+}
+
+pub async fn write_response_with_type(
+    stream: &mut TcpStream,
+    status: u16,
+    content_type: &str,
+    body: &str,
+) -> io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        reason(status),
+        content_type,
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.flush().await
+}
+
+// percent-encoding, with + for spaces like browsers send forms
+fn decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let hex = |byte: u8| (byte as char).to_digit(16).map(|digit| digit as u8);
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => match (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                (Some(high), Some(low)) => {
+                    decoded.push(high * 16 + low);
+                    i += 2;
+                }
+                _ => decoded.push(b'%'),
+            },
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        101 => "Switching Protocols",
+        202 => "Accepted",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "",
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,106 @@
+// the bot itself, without any user interface, for embedding it in other programs
+// connect with Bot::connect, then read the Events and change the settings as needed
+
+mod anti_afk;
+pub mod api;
+mod asyncio;
+pub mod behavior;
+pub mod behaviors;
+mod blocks;
+mod bot;
+mod chat;
+mod client;
+mod clone_all;
+pub mod config;
+pub mod datatypes;
+pub mod export;
+pub mod foods;
+mod handle;
+mod http;
+pub mod logger;
+pub mod manager;
+pub mod metrics;
+pub mod mobs;
+mod objects;
+pub mod packets;
+mod pathfinding;
+mod physics;
+mod registries;
+mod remote;
+pub mod schedule;
+pub mod scripts;
+mod websocket;
+pub mod webhooks;
+mod world;
+
+use asyncio::*;
+pub use behavior::{Behavior, Context};
+pub use bot::{Disconnect, Mob, Player, State};
+pub use handle::{Bot, Event, Events, Request, Status};
+pub use log::Level; // of the log events
+use schedule::ScheduledMessage;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+pub const PROTOCOL: i64 = 754;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub sneak: bool,
+    pub anti_afk: bool,
+    pub attack_mobs: Vec<i64>,
+    pub eat_food: Vec<i64>,
+    pub attack_speed: f32, // attacks/second
+    pub weapon: u8,
+    pub min_health: f32,
+    pub eat_at: f32,
+    pub auto_respawn: bool,
+    pub line_of_sight_servers: HashSet<String>, // addresses of the servers where mobs behind blocks aren't attacked
+    pub return_to_spot: bool, // walk back to the AFK spot after being knocked away
+    pub goto: Option<(i32, i32, i32)>, // the block to walk to
+    pub goto_on_join: bool,
+    #[serde(skip)]
+    pub goto_now: bool, // set to start going right away, reset by the bot once it starts going
+    pub statistics_file: String, // .csv or .jsonl file to export the statistics to, empty if not exporting
+    pub login_passwords: HashMap<String, String>, // server address -> password for auth plugins
+    pub owners: Vec<String>,     // players allowed to control the bot with /msg
+    pub schedule: String,        // the text of the schedule, one message per line
+    pub behaviors: Vec<String>,  // the built-in behaviors to run, by name
+    pub scripts_folder: String,  // where to load the .rhai scripts from, empty if not running any
+    pub player_distance: f64,    // report other players that come this close, 0 to not watch for them
+    #[serde(skip)]
+    pub scheduled_messages: Vec<ScheduledMessage>, // parsed from the schedule
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            sneak: false,
+            anti_afk: false,
+            attack_mobs: Vec::new(),
+            eat_food: Vec::new(),
+            attack_speed: 0.0,
+            weapon: 0,
+            min_health: 6.0,
+            eat_at: 10.0,
+            auto_respawn: false,
+            line_of_sight_servers: HashSet::new(),
+            return_to_spot: true,
+            goto: None,
+            goto_on_join: false,
+            goto_now: false,
+            statistics_file: String::new(),
+            login_passwords: HashMap::new(),
+            owners: Vec::new(),
+            schedule: String::new(),
+            behaviors: behaviors::BUILT_IN
+                .iter()
+                .map(|(name, _)| name.to_string())
+                .collect(),
+            scripts_folder: String::new(),
+            player_distance: 16.0,
+            scheduled_messages: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,198 @@
+// the log of the whole app, written through the log crate's macros
+// every line goes to a file on disk that's rotated once it's too big, and to whoever listens, like the window
+
+use chrono::Local;
+use log::{LevelFilter, Log, Metadata, Record};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogSettings {
+    pub level: String, // "error", "warn", "info", "debug" or "trace", the least important
+    pub file: String,  // next to the executable if not absolute, empty to not keep the log
+    pub max_size: u64, // KB, a new file is started after that
+    pub keep: usize,   // how many of the older files to keep
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            file: "mc_afk_bot.log".to_string(),
+            max_size: 10 * 1024,
+            keep: 5,
+        }
+    }
+}
+
+impl LogSettings {
+    pub fn path(&self) -> Option<PathBuf> {
+        if self.file.is_empty() {
+            return None;
+        }
+        let path = PathBuf::from(&self.file);
+        if path.is_absolute() {
+            return Some(path);
+        }
+        Some(
+            std::env::current_exe()
+                .ok()
+                .and_then(|exe| exe.parent().map(|dir| dir.join(&path)))
+                .unwrap_or(path),
+        )
+    }
+}
+
+type Listener = Box<dyn FnMut(&str) + Send>;
+
+pub struct Logger {
+    level: LevelFilter,
+    stdout: bool,
+    file: Option<Mutex<RotatingFile>>,
+    listeners: Mutex<Vec<Listener>>,
+}
+
+impl Logger {
+    // sets up the log for the whole app, the file is given separately so that it can be overridden
+    // the logger is still set up when something fails, the errors are returned so that they can be logged
+    pub fn init(
+        settings: &LogSettings,
+        file: Option<PathBuf>,
+        stdout: bool,
+    ) -> (&'static Logger, Vec<String>) {
+        let mut errors = Vec::new();
+        let level = match settings.level.parse::<LevelFilter>() {
+            Ok(level) => level,
+            Err(_) => {
+                errors.push(format!("Unknown log level {:?}", settings.level));
+                LevelFilter::Info
+            }
+        };
+        let file = file.and_then(|path| {
+            match RotatingFile::open(path.clone(), settings.max_size * 1024, settings.keep) {
+                Ok(file) => Some(Mutex::new(file)),
+                Err(e) => {
+                    errors.push(format!("Couldn't open {}: {}", path.display(), e));
+                    None
+                }
+            }
+        });
+
+        // it's needed until the app exits anyway
+        let logger: &'static Logger = Box::leak(Box::new(Logger {
+            level,
+            stdout,
+            file,
+            listeners: Mutex::new(Vec::new()),
+        }));
+        // fails only if it was already set up
+        if log::set_logger(logger).is_ok() {
+            log::set_max_level(level);
+        }
+        (logger, errors)
+    }
+
+    // called with every line that's logged from now on, from whichever thread logged it
+    pub fn listen<F: FnMut(&str) + Send + 'static>(&self, listener: F) {
+        self.listeners.lock().unwrap().push(Box::new(listener));
+    }
+}
+
+impl Log for Logger {
+    // the libraries log too, but only this app's lines are worth showing
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level && metadata.target().starts_with("mc_afk_bot")
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = format!(
+            "[{}] {:<5} {}",
+            Local::now().format("%F %T"),
+            record.level(),
+            record.args()
+        );
+        if self.stdout {
+            println!("{}", line);
+        }
+        if let Some(file) = &self.file {
+            // not much else to do if the log can't be written to
+            let _ = file.lock().unwrap().write_line(&line);
+        }
+        for listener in self.listeners.lock().unwrap().iter_mut() {
+            listener(&line);
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(file) = &self.file {
+            let _ = file.lock().unwrap().file.flush();
+        }
+    }
+}
+
+// mc_afk_bot.log becomes mc_afk_bot.log.1 when it's full, the one before becomes mc_afk_bot.log.2 and so on
+struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max_size: u64,
+    keep: usize,
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf, max_size: u64, keep: usize) -> io::Result<RotatingFile> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(RotatingFile {
+            path,
+            file,
+            size,
+            max_size,
+            keep,
+        })
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        let length = line.len() as u64 + 1;
+        if self.size > 0 && self.size + length > self.max_size {
+            self.rotate()?;
+        }
+        writeln!(self.file, "{}", line)?;
+        self.size += length;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        if self.keep > 0 {
+            // renaming onto an existing file fails on Windows
+            let _ = fs::remove_file(self.numbered(self.keep));
+            for i in (1..self.keep).rev() {
+                let _ = fs::rename(self.numbered(i), self.numbered(i + 1));
+            }
+            fs::rename(&self.path, self.numbered(1))?;
+        }
+        self.file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+
+    fn numbered(&self, i: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{}", i));
+        PathBuf::from(path)
+    }
+}
@@ -0,0 +1,118 @@
+// keeps track of the bots that are running at the same time, each with its own account and settings
+
+use crate::export::Row;
+use crate::handle::{Bot, Event, Status};
+use crate::webhooks::Webhooks;
+use std::collections::{BTreeMap, HashMap};
+use tokio::sync::broadcast;
+
+// how many events a slow listener can fall behind before it misses some
+const FEED_CAPACITY: usize = 256;
+
+pub struct Session {
+    pub address: String,
+    pub username: String,
+    pub bot: Bot,
+    pub status: Option<Status>, // none until the server sends the health
+    pub statistics: Vec<Row>,   // the last ones the bot got
+}
+
+pub struct Manager {
+    next_id: u32,
+    sessions: BTreeMap<u32, Session>, // by id, so in the order they were started
+    feed: broadcast::Sender<(u32, Event)>, // the events of all the bots, with their ids
+    webhooks: Option<Webhooks>,       // where the important events are posted, if anywhere
+    connections: HashMap<(String, String), u64>, // how often each account was connected to each server
+}
+
+impl Default for Manager {
+    fn default() -> Self {
+        Self {
+            next_id: 0,
+            sessions: BTreeMap::new(),
+            feed: broadcast::channel(FEED_CAPACITY).0,
+            webhooks: None,
+            connections: HashMap::new(),
+        }
+    }
+}
+
+impl Manager {
+    // the server would kick one of them anyway, so they shouldn't be started twice
+    pub fn is_running(&self, address: &str, username: &str) -> bool {
+        self.sessions.values().any(|running| {
+            running.address == address && running.username.eq_ignore_ascii_case(username)
+        })
+    }
+
+    // returns the id of the new session
+    pub fn add(&mut self, session: Session) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        let account = (session.address.clone(), session.username.to_lowercase());
+        *self.connections.entry(account).or_insert(0) += 1;
+        self.sessions.insert(id, session);
+        id
+    }
+
+    // the times the account was connected to the server again after the first time
+    pub fn reconnects(&self, address: &str, username: &str) -> u64 {
+        let account = (address.to_string(), username.to_lowercase());
+        match self.connections.get(&account) {
+            Some(connections) => connections - 1,
+            None => 0,
+        }
+    }
+
+    pub fn remove(&mut self, id: u32) -> Option<Session> {
+        self.sessions.remove(&id)
+    }
+
+    pub fn get(&self, id: u32) -> Option<&Session> {
+        self.sessions.get(&id)
+    }
+
+    pub fn set_status(&mut self, id: u32, status: Status) {
+        if let Some(session) = self.sessions.get_mut(&id) {
+            session.status = Some(status);
+        }
+    }
+
+    pub fn set_statistics(&mut self, id: u32, statistics: Vec<Row>) {
+        if let Some(session) = self.sessions.get_mut(&id) {
+            session.statistics = statistics;
+        }
+    }
+
+    // passes an event on to everyone listening, e.g. the API's event feed and the webhooks
+    pub fn publish(&self, id: u32, event: &Event) {
+        if let (Some(webhooks), Some(session)) = (&self.webhooks, self.sessions.get(&id)) {
+            webhooks.notify(&session.username, event);
+        }
+        // fails only if nobody is listening
+        let _ = self.feed.send((id, event.clone()));
+    }
+
+    // dropping them lets their task post what's left and finish
+    pub fn set_webhooks(&mut self, webhooks: Option<Webhooks>) {
+        self.webhooks = webhooks;
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<(u32, Event)> {
+        self.feed.subscribe()
+    }
+
+    // all of them with their ids, in the order they were started
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &Session)> {
+        self.sessions.iter().map(|(&id, session)| (id, session))
+    }
+
+    // the index of the session among the running ones
+    pub fn position(&self, id: u32) -> Option<usize> {
+        self.sessions.keys().position(|&key| key == id)
+    }
+
+    pub fn nth(&self, index: usize) -> Option<&Session> {
+        self.sessions.values().nth(index)
+    }
+}
@@ -0,0 +1,157 @@
+// the numbers of every bot in the Prometheus text format, served by the API at /metrics
+
+use crate::bot;
+use crate::datatypes::VarInt;
+use crate::manager::Manager;
+use crate::packets::{ClientBound, ServerBound};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+// counted by the connection as packets come and go, the rest is read from the state when scraped
+#[derive(Debug, Default)]
+pub struct Metrics {
+    received: Mutex<BTreeMap<&'static str, (u64, u64)>>, // packet type -> packets, bytes
+    sent: Mutex<BTreeMap<&'static str, (u64, u64)>>,
+    attacks: AtomicU64,
+}
+
+impl Metrics {
+    // the bytes are of the packet id and data, before compressing
+    pub(crate) fn received(&self, packet: &ClientBound, bytes: u64) {
+        let mut received = self.received.lock().unwrap();
+        let counts = received.entry(packet.name()).or_default();
+        counts.0 += 1;
+        counts.1 += bytes;
+    }
+
+    pub(crate) fn sent(&self, packet: &ServerBound, bytes: u64) {
+        if let ServerBound::InteractEntity(_, VarInt(1), _) = packet {
+            self.attacks.fetch_add(1, Ordering::Relaxed);
+        }
+        let mut sent = self.sent.lock().unwrap();
+        let counts = sent.entry(packet.name()).or_default();
+        counts.0 += 1;
+        counts.1 += bytes;
+    }
+}
+
+// name, type, help
+const FAMILIES: [(&str, &str, &str); 13] = [
+    ("health", "gauge", "Health, out of 20"),
+    ("food", "gauge", "Food, out of 20"),
+    ("saturation", "gauge", "Food saturation"),
+    ("mobs_tracked", "gauge", "Mobs the bot knows about"),
+    (
+        "kills_total",
+        "counter",
+        "Mobs killed since connecting, by type",
+    ),
+    (
+        "attacks_sent_total",
+        "counter",
+        "Attacks sent to the server",
+    ),
+    (
+        "packets_received_total",
+        "counter",
+        "Packets received, by type",
+    ),
+    (
+        "bytes_received_total",
+        "counter",
+        "Uncompressed bytes received, by packet type",
+    ),
+    ("packets_sent_total", "counter", "Packets sent, by type"),
+    (
+        "bytes_sent_total",
+        "counter",
+        "Uncompressed bytes sent, by packet type",
+    ),
+    (
+        "reconnects_total",
+        "counter",
+        "Times the same account was connected to the same server again since the app started",
+    ),
+    ("uptime_seconds", "gauge", "Seconds since connecting"),
+    ("bots", "gauge", "Bots running"),
+];
+
+pub async fn render(manager: &Arc<Mutex<Manager>>) -> String {
+    let bots: Vec<_> = {
+        let manager_lock = manager.lock().unwrap();
+        manager_lock
+            .iter()
+            .map(|(id, session)| {
+                let labels = format!(
+                    "id=\"{}\",bot=\"{}\",server=\"{}\"",
+                    id,
+                    escape(&session.username),
+                    escape(&session.address)
+                );
+                let reconnects = manager_lock.reconnects(&session.address, &session.username);
+                (labels, session.bot.clone(), reconnects)
+            })
+            .collect()
+    };
+
+    // the samples of every family, so that each is written together under its help and type
+    let mut samples: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    let mut sample = |family: &'static str, labels: &str, value: f64| {
+        let line = match labels {
+            "" => format!("mc_afk_bot_{} {}", family, value),
+            labels => format!("mc_afk_bot_{}{{{}}} {}", family, labels, value),
+        };
+        samples.entry(family).or_default().push(line);
+    };
+    for (labels, bot, reconnects) in &bots {
+        {
+            let state_lock = bot.state().lock().await;
+            sample("health", labels, state_lock.health as f64);
+            sample("food", labels, state_lock.food as f64);
+            sample("saturation", labels, state_lock.saturation as f64);
+            sample("mobs_tracked", labels, state_lock.mobs.len() as f64);
+            for (mob, kills) in bot::kills_by_mob(&state_lock) {
+                let labels = format!("{},mob=\"{}\"", labels, escape(&mob));
+                sample("kills_total", &labels, kills as f64);
+            }
+            let uptime = state_lock.joined_at.elapsed().as_secs_f64();
+            sample("uptime_seconds", labels, uptime);
+        }
+
+        let metrics = bot.metrics();
+        let attacks = metrics.attacks.load(Ordering::Relaxed);
+        sample("attacks_sent_total", labels, attacks as f64);
+        for (packet, (count, bytes)) in metrics.received.lock().unwrap().iter() {
+            let labels = format!("{},type=\"{}\"", labels, packet);
+            sample("packets_received_total", &labels, *count as f64);
+            sample("bytes_received_total", &labels, *bytes as f64);
+        }
+        for (packet, (count, bytes)) in metrics.sent.lock().unwrap().iter() {
+            let labels = format!("{},type=\"{}\"", labels, packet);
+            sample("packets_sent_total", &labels, *count as f64);
+            sample("bytes_sent_total", &labels, *bytes as f64);
+        }
+        sample("reconnects_total", labels, *reconnects as f64);
+    }
+    sample("bots", "", bots.len() as f64);
+
+    let mut text = String::new();
+    for (family, kind, help) in FAMILIES.iter() {
+        let _ = writeln!(text, "# HELP mc_afk_bot_{} {}", family, help);
+        let _ = writeln!(text, "# TYPE mc_afk_bot_{} {}", family, kind);
+        for line in samples.get(family).into_iter().flatten() {
+            let _ = writeln!(text, "{}", line);
+        }
+    }
+    text
+}
+
+// label values are quoted, so quotes, backslashes and new lines have to be escaped
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
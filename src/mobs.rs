@@ -1,4 +1,4 @@
-// maps mob names to their IDs and vice versa
+// maps mob names to their IDs and vice versa, and knows how tall they are
 
 use bimap::BiBTreeMap;
 use lazy_static::lazy_static;
@@ -80,3 +80,47 @@ lazy_static! {
         map
     };
 }
+
+// the height of the mob's hitbox in blocks, adults only
+pub fn height(id: i64) -> f64 {
+    match id {
+        3 => 0.9,           // Bat
+        4 => 0.6,           // Bee
+        7 | 28 | 54 => 0.7, // Cat, Fox, Ocelot
+        8 => 0.5,           // Cave Spider
+        9 => 0.7,           // Chicken
+        10 | 90 => 0.4,     // Cod, Tropical Fish
+        11 | 53 => 1.4,     // Cow, Mushroom
+        12 => 1.7,          // Creeper
+        13 => 0.6,          // Dolphin
+        17 => 2.0,          // Elder Guardian
+        19 => 8.0,          // Ender Dragon
+        20 => 2.9,          // Enderman
+        21 | 72 => 0.3,     // Endermite, Silverfish
+        23 => 0.8,          // Evoker Fangs
+        29 => 4.0,          // Ghast
+        30 => 12.0,         // Giant
+        31 => 0.85,         // Guardian
+        32 | 101 => 1.4,    // Hoglin, Zoglin
+        44 | 75 => 0.5,     // Magma Cube, Slime (the smallest ones)
+        56 => 1.25,         // Panda
+        57 => 0.9,          // Parrot
+        58 => 0.5,          // Phantom
+        59 => 0.9,          // Pig
+        63 => 1.4,          // Polar Bear
+        65 => 0.7,          // Pufferfish
+        66 => 0.5,          // Rabbit
+        67 => 2.2,          // Ravager
+        68 => 0.4,          // Salmon
+        69 => 1.3,          // Sheep
+        70 => 1.0,          // Shulker
+        80 => 0.9,          // Spider
+        81 => 0.8,          // Squid
+        91 => 0.4,          // Turtle
+        92 => 0.8,          // Vex
+        97 => 3.5,          // Wither
+        98 => 2.4,          // Wither Skeleton
+        100 => 0.85,        // Wolf
+        _ => 1.95,          // most humanoid mobs
+    }
+}
@@ -0,0 +1,144 @@
+// non-living entities, like dropped items, experience orbs and projectiles
+// the IDs are only valid for protocol 754 (1.16.5)
+
+use crate::datatypes::*;
+use std::io::Cursor;
+use tokio::io;
+
+// the metadata index of the item that a dropped item entity is
+const ITEM_INDEX: u8 = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Item,
+    ExperienceOrb(i16), // amount of experience
+    Arrow,
+    Tnt,
+    FallingBlock,
+    Fireball,
+    Other,
+}
+
+pub fn kind(entity_type: i64) -> Kind {
+    match entity_type {
+        37 => Kind::Item,
+        2 | 79 | 88 => Kind::Arrow, // Arrow, Spectral Arrow, Trident
+        64 => Kind::Tnt,
+        26 => Kind::FallingBlock,
+        15 | 39 | 76 | 99 => Kind::Fireball, // Dragon Fireball, Fireball, Small Fireball, Wither Skull
+        _ => Kind::Other,
+    }
+}
+
+// in how many ticks the projectile will be closest to the target and how close it will be,
+// ignoring gravity and drag, which don't matter much over short distances
+pub fn closest_approach(
+    pos: (f64, f64, f64),
+    velocity: (f64, f64, f64),
+    target: (f64, f64, f64),
+) -> (f64, f64) {
+    let relative = (target.0 - pos.0, target.1 - pos.1, target.2 - pos.2);
+    let speed_squared = velocity.0.powi(2) + velocity.1.powi(2) + velocity.2.powi(2);
+    if speed_squared == 0.0 {
+        return (0.0, length(relative));
+    }
+
+    let ticks = ((relative.0 * velocity.0 + relative.1 * velocity.1 + relative.2 * velocity.2)
+        / speed_squared)
+        .max(0.0);
+    let distance = length((
+        relative.0 - velocity.0 * ticks,
+        relative.1 - velocity.1 * ticks,
+        relative.2 - velocity.2 * ticks,
+    ));
+
+    (ticks, distance)
+}
+
+// finds the item in the metadata of a dropped item entity
+// all info available on https://wiki.vg/index.php?title=Entity_metadata&oldid=16539
+pub async fn item_from_metadata(metadata: Vec<u8>) -> io::Result<Option<(i64, i8)>> {
+    let mut input = Cursor::new(metadata);
+
+    loop {
+        let index = u8::deserialize(&mut input).await?;
+        if index == 0xff {
+            return Ok(None);
+        }
+        let value_type = VarInt::deserialize(&mut input).await?;
+
+        // skip all values before the item
+        match value_type.0 {
+            0 | 7 => {
+                // byte, boolean
+                u8::deserialize(&mut input).await?;
+            }
+            1 | 11 | 13 | 17 | 18 => {
+                // varint, direction, optional block id, optional varint, pose
+                VarInt::deserialize(&mut input).await?;
+            }
+            2 => {
+                f32::deserialize(&mut input).await?;
+            }
+            3 | 4 => {
+                // string, chat
+                MString::deserialize(&mut input).await?;
+            }
+            5 => {
+                // optional chat
+                if bool::deserialize(&mut input).await? {
+                    MString::deserialize(&mut input).await?;
+                }
+            }
+            6 => {
+                let slot = Slot::deserialize(&mut input).await?;
+                if index == ITEM_INDEX {
+                    return Ok(match slot {
+                        Slot::Present(id, count) => Some((id.0, count)),
+                        Slot::NotPresent => None,
+                    });
+                }
+                if let Slot::Present(..) = slot {
+                    skip_nbt(&mut input).await?;
+                }
+            }
+            8 => {
+                // rotation
+                for _ in 0..3 {
+                    f32::deserialize(&mut input).await?;
+                }
+            }
+            9 => {
+                // position
+                i64::deserialize(&mut input).await?;
+            }
+            10 => {
+                // optional position
+                if bool::deserialize(&mut input).await? {
+                    i64::deserialize(&mut input).await?;
+                }
+            }
+            12 => {
+                // optional uuid
+                if bool::deserialize(&mut input).await? {
+                    u128::deserialize(&mut input).await?;
+                }
+            }
+            14 => {
+                skip_nbt(&mut input).await?;
+            }
+            16 => {
+                // villager data
+                for _ in 0..3 {
+                    VarInt::deserialize(&mut input).await?;
+                }
+            }
+            // particles and anything unknown can't be skipped
+            _ => return Ok(None),
+        }
+    }
+}
+
+fn length(v: (f64, f64, f64)) -> f64 {
+    (v.0.powi(2) + v.1.powi(2) + v.2.powi(2)).sqrt()
+}
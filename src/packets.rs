@@ -1,6 +1,7 @@
 use crate::asyncio::SizeCalc;
 use crate::datatypes::*;
 use crate::{MyAsyncRead, MyAsyncWrite};
+use std::io::Cursor;
 use tokio::io;
 
 // Sent from the client to the server
@@ -14,7 +15,9 @@ pub enum ServerBound {
     ChatMessage(MString), // the raw message, up to 256 characters
     ClientStatus(VarInt), // 0 - respawn, 1 - request statistics
     InteractEntity(VarInt, VarInt, bool), // entity id, [0 - interact, 1 - attack, 2 - interact at (not supported)], whether sneaking
+    PlayerPosition(f64, f64, f64, bool),  // x, feet y, z, whether on ground
     PlayerPositionAndRotation(f64, f64, f64, f32, f32, bool), // x, y, z, yaw, pitch, whether on ground
+    PlayerRotation(f32, f32, bool),                           // yaw, pitch, whether on ground
     Animation(VarInt),                                        // 0 - main hand, 1 - off hand
     TeleportConfirm(VarInt),                                  // teleport id
     EntityAction(VarInt, VarInt, VarInt), // player's entity id, action (see https://wiki.vg/index.php?title=Protocol&oldid=16091#Entity_Action), jump boost (only for jumping with horse)
@@ -34,6 +37,23 @@ pub enum ClientBound {
     PlayDisconnect(MString),
     UpdateHealth(f32, VarInt, f32), // health, food, saturation
     PlayerPositionAndLook(f64, f64, f64, f32, f32, u8, VarInt), // x, y, z, yaw, pitch, flags, tp id
+    SpawnEntity(
+        VarInt,
+        u128,
+        VarInt,
+        f64,
+        f64,
+        f64,
+        u8,
+        u8,
+        i32,
+        i16,
+        i16,
+        i16,
+    ), // entity id, uuid, type, x, y, z, pitch, yaw, data (depends on the type), velocity: x, y, z
+    SpawnExperienceOrb(VarInt, f64, f64, f64, i16), // entity id, x, y, z, amount of experience
+    SpawnPlayer(VarInt, u128, f64, f64, f64), // entity id, UUID, x, y, z
+    PlayerInfo(Vec<(u128, MString)>), // UUIDs and names of the players added to the tab list, other changes are skipped
     SpawnLivingEntity(
         VarInt,
         u128,
@@ -54,10 +74,44 @@ pub enum ClientBound {
     JoinGame(i32), // this has lots of other data, but we're reading only the entity id
     SetSlot(i8, i16, Slot), // window id, slot id, slot data
     Statistics(Vec<(VarInt, VarInt, VarInt)>), // Category, id, value
-    Unknown(VarInt), // the packet id of the unknown packet
+    ChatMessage(MString, u8, u128), // JSON data, position (0 - chat, 1 - system, 2 - game info), sender UUID
+    CombatEvent(VarInt, Option<(VarInt, i32, MString)>), // event (0 - enter combat, 1 - end combat, 2 - entity dead), for entity dead: player id, killer entity id, death message
+    Respawn, // this has lots of data, but we only need to know that it happened
+    EntityVelocity(VarInt, i16, i16, i16), // entity id, velocity x, y, z in 1/8000 of a block per tick
+    Explosion(f32, f32, f32, Vec<(i8, i8, i8)>, f32, f32, f32), // x, y, z, destroyed blocks relative to x, y, z, player motion x, y, z
+    ChunkData(i32, i32, bool, VarInt, Vec<u8>), // chunk x, z, whether it's a full chunk, primary bit mask, chunk sections data
+    UnloadChunk(i32, i32),                      // chunk x, z
+    BlockChange(i64, VarInt),                   // position, block state id
+    MultiBlockChange(i64, Vec<VarInt>), // chunk section position, blocks (block state id << 12 | x << 8 | z << 4 | y)
+    EntityMetadata(VarInt, Vec<u8>), // entity id, the raw metadata, since it can't always be parsed fully
+    CollectItem(VarInt, VarInt, VarInt), // collected entity id, collector entity id, item count
+    SetExperience(f32, VarInt, VarInt), // experience bar, level, total experience
+    Unknown(VarInt),                 // the packet id of the unknown packet
 }
 
 impl ServerBound {
+    // for the metrics
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Handshake(..) => "handshake",
+            Self::StatusRequest => "status_request",
+            Self::LoginStart(..) => "login_start",
+            Self::KeepAlive(..) => "keep_alive",
+            Self::ChatMessage(..) => "chat_message",
+            Self::ClientStatus(..) => "client_status",
+            Self::InteractEntity(..) => "interact_entity",
+            Self::PlayerPosition(..) => "player_position",
+            Self::PlayerPositionAndRotation(..) => "player_position_and_rotation",
+            Self::PlayerRotation(..) => "player_rotation",
+            Self::Animation(..) => "animation",
+            Self::TeleportConfirm(..) => "teleport_confirm",
+            Self::EntityAction(..) => "entity_action",
+            Self::HeldItemChange(..) => "held_item_change",
+            Self::UseItem(..) => "use_item",
+            Self::PlayerDigging(..) => "player_digging",
+        }
+    }
+
     pub async fn gen_to<O: MyAsyncWrite + Send + 'static>(self, output: &mut O) -> io::Result<()> {
         match self {
             Self::Handshake(protocol, address, port, next_state) => {
@@ -108,6 +162,21 @@ impl ServerBound {
                 pitch.serialize(output).await?;
                 on_ground.serialize(output).await?;
             }
+            Self::PlayerPosition(x, y, z, on_ground) => {
+                VarInt(0x12).serialize(output).await?;
+
+                x.serialize(output).await?;
+                y.serialize(output).await?;
+                z.serialize(output).await?;
+                on_ground.serialize(output).await?;
+            }
+            Self::PlayerRotation(yaw, pitch, on_ground) => {
+                VarInt(0x14).serialize(output).await?;
+
+                yaw.serialize(output).await?;
+                pitch.serialize(output).await?;
+                on_ground.serialize(output).await?;
+            }
             Self::Animation(hand) => {
                 VarInt(0x2c).serialize(output).await?;
 
@@ -149,6 +218,44 @@ impl ServerBound {
 }
 
 impl ClientBound {
+    // for the metrics, the unknown ones are counted together
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::LoginDisconnect(..) => "login_disconnect",
+            Self::StatusResponse(..) => "status_response",
+            Self::SetCompression(..) => "set_compression",
+            Self::LoginSuccess(..) => "login_success",
+            Self::KeepAlive(..) => "keep_alive",
+            Self::PlayDisconnect(..) => "play_disconnect",
+            Self::UpdateHealth(..) => "update_health",
+            Self::PlayerPositionAndLook(..) => "player_position_and_look",
+            Self::SpawnEntity(..) => "spawn_entity",
+            Self::SpawnExperienceOrb(..) => "spawn_experience_orb",
+            Self::SpawnPlayer(..) => "spawn_player",
+            Self::PlayerInfo(..) => "player_info",
+            Self::SpawnLivingEntity(..) => "spawn_living_entity",
+            Self::EntityTeleport(..) => "entity_teleport",
+            Self::EntityPosition(..) => "entity_position",
+            Self::DestroyEntities(..) => "destroy_entities",
+            Self::JoinGame(..) => "join_game",
+            Self::SetSlot(..) => "set_slot",
+            Self::Statistics(..) => "statistics",
+            Self::ChatMessage(..) => "chat_message",
+            Self::CombatEvent(..) => "combat_event",
+            Self::Respawn => "respawn",
+            Self::EntityVelocity(..) => "entity_velocity",
+            Self::Explosion(..) => "explosion",
+            Self::ChunkData(..) => "chunk_data",
+            Self::UnloadChunk(..) => "unload_chunk",
+            Self::BlockChange(..) => "block_change",
+            Self::MultiBlockChange(..) => "multi_block_change",
+            Self::EntityMetadata(..) => "entity_metadata",
+            Self::CollectItem(..) => "collect_item",
+            Self::SetExperience(..) => "set_experience",
+            Self::Unknown(..) => "unknown",
+        }
+    }
+
     pub async fn read_from<S: MyAsyncRead + Send + 'static>(
         input: &mut S,
         length: i64,
@@ -202,6 +309,27 @@ impl ClientBound {
                         u8::deserialize(input).await?,
                         VarInt::deserialize(input).await?,
                     )),
+                    0x00 => Ok(Self::SpawnEntity(
+                        VarInt::deserialize(input).await?,
+                        u128::deserialize(input).await?,
+                        VarInt::deserialize(input).await?,
+                        f64::deserialize(input).await?,
+                        f64::deserialize(input).await?,
+                        f64::deserialize(input).await?,
+                        u8::deserialize(input).await?,
+                        u8::deserialize(input).await?,
+                        i32::deserialize(input).await?,
+                        i16::deserialize(input).await?,
+                        i16::deserialize(input).await?,
+                        i16::deserialize(input).await?,
+                    )),
+                    0x01 => Ok(Self::SpawnExperienceOrb(
+                        VarInt::deserialize(input).await?,
+                        f64::deserialize(input).await?,
+                        f64::deserialize(input).await?,
+                        f64::deserialize(input).await?,
+                        i16::deserialize(input).await?,
+                    )),
                     0x02 => Ok(Self::SpawnLivingEntity(
                         VarInt::deserialize(input).await?,
                         u128::deserialize(input).await?,
@@ -225,6 +353,29 @@ impl ClientBound {
                         u8::deserialize(input).await?,
                         bool::deserialize(input).await?,
                     )),
+                    0x04 => {
+                        let entity_id = VarInt::deserialize(input).await?;
+                        let uuid = u128::deserialize(input).await?;
+                        let x = f64::deserialize(input).await?;
+                        let y = f64::deserialize(input).await?;
+                        let z = f64::deserialize(input).await?;
+                        // yaw, pitch
+                        u8::deserialize(input).await?;
+                        u8::deserialize(input).await?;
+
+                        Ok(Self::SpawnPlayer(entity_id, uuid, x, y, z))
+                    }
+                    0x32 => {
+                        // read it whole first, so that the rest is skipped even if it can't be parsed
+                        let size = length as usize - VarInt(packet_id).size() as usize;
+                        let mut data = vec![0u8; size];
+                        input.read(&mut data).await?;
+
+                        let mut data = Cursor::new(data);
+                        Ok(Self::PlayerInfo(
+                            read_added_players(&mut data).await.unwrap_or_default(),
+                        ))
+                    }
                     0x27 => Ok(Self::EntityPosition(
                         VarInt::deserialize(input).await?,
                         i16::deserialize(input).await?,
@@ -232,6 +383,24 @@ impl ClientBound {
                         i16::deserialize(input).await?,
                         bool::deserialize(input).await?,
                     )),
+                    0x28 => {
+                        // entity position and rotation, the rotation is not needed
+                        let entity_id = VarInt::deserialize(input).await?;
+                        let delta_x = i16::deserialize(input).await?;
+                        let delta_y = i16::deserialize(input).await?;
+                        let delta_z = i16::deserialize(input).await?;
+                        // yaw, pitch
+                        u8::deserialize(input).await?;
+                        u8::deserialize(input).await?;
+
+                        Ok(Self::EntityPosition(
+                            entity_id,
+                            delta_x,
+                            delta_y,
+                            delta_z,
+                            bool::deserialize(input).await?,
+                        ))
+                    }
                     0x36 => Ok(Self::DestroyEntities(
                         Vec::<VarInt>::deserialize(input).await?,
                     )),
@@ -280,6 +449,134 @@ impl ClientBound {
 
                         Ok(Self::Statistics(data))
                     }
+                    0x31 => {
+                        let event = VarInt::deserialize(input).await?;
+                        match event.0 {
+                            1 => {
+                                // end combat has the duration and the entity id, which we don't need
+                                VarInt::deserialize(input).await?;
+                                i32::deserialize(input).await?;
+
+                                Ok(Self::CombatEvent(event, None))
+                            }
+                            2 => Ok(Self::CombatEvent(
+                                event,
+                                Some((
+                                    VarInt::deserialize(input).await?,
+                                    i32::deserialize(input).await?,
+                                    MString::deserialize(input).await?,
+                                )),
+                            )),
+                            _ => Ok(Self::CombatEvent(event, None)),
+                        }
+                    }
+                    0x39 => {
+                        // read to end
+                        let size = length as usize - VarInt(packet_id).size() as usize;
+                        let mut garbage = vec![0u8; size];
+
+                        input.read(&mut garbage).await?;
+
+                        Ok(Self::Respawn)
+                    }
+                    0x46 => Ok(Self::EntityVelocity(
+                        VarInt::deserialize(input).await?,
+                        i16::deserialize(input).await?,
+                        i16::deserialize(input).await?,
+                        i16::deserialize(input).await?,
+                    )),
+                    0x1B => {
+                        let x = f32::deserialize(input).await?;
+                        let y = f32::deserialize(input).await?;
+                        let z = f32::deserialize(input).await?;
+                        // strength
+                        f32::deserialize(input).await?;
+                        let records = i32::deserialize(input).await?;
+                        let mut destroyed = Vec::with_capacity(records.max(0) as usize);
+                        for _ in 0..records {
+                            destroyed.push((
+                                i8::deserialize(input).await?,
+                                i8::deserialize(input).await?,
+                                i8::deserialize(input).await?,
+                            ));
+                        }
+
+                        Ok(Self::Explosion(
+                            x,
+                            y,
+                            z,
+                            destroyed,
+                            f32::deserialize(input).await?,
+                            f32::deserialize(input).await?,
+                            f32::deserialize(input).await?,
+                        ))
+                    }
+                    0x20 => {
+                        let x = i32::deserialize(input).await?;
+                        let z = i32::deserialize(input).await?;
+                        let full_chunk = bool::deserialize(input).await?;
+                        let primary_bit_mask = VarInt::deserialize(input).await?;
+                        // heightmaps
+                        skip_nbt(input).await?;
+                        if full_chunk {
+                            // biomes
+                            let biomes = VarInt::deserialize(input).await?;
+                            for _ in 0..biomes.0 {
+                                VarInt::deserialize(input).await?;
+                            }
+                        }
+                        let size = VarInt::deserialize(input).await?;
+                        let mut data = vec![0u8; size.0 as usize];
+                        input.read(&mut data).await?;
+                        // block entities
+                        let block_entities = VarInt::deserialize(input).await?;
+                        for _ in 0..block_entities.0 {
+                            skip_nbt(input).await?;
+                        }
+
+                        Ok(Self::ChunkData(x, z, full_chunk, primary_bit_mask, data))
+                    }
+                    0x1C => Ok(Self::UnloadChunk(
+                        i32::deserialize(input).await?,
+                        i32::deserialize(input).await?,
+                    )),
+                    0x0B => Ok(Self::BlockChange(
+                        i64::deserialize(input).await?,
+                        VarInt::deserialize(input).await?,
+                    )),
+                    0x3B => {
+                        let section_position = i64::deserialize(input).await?;
+                        // whether to trust edges (light related)
+                        bool::deserialize(input).await?;
+                        let blocks = Vec::<VarInt>::deserialize(input).await?;
+
+                        Ok(Self::MultiBlockChange(section_position, blocks))
+                    }
+                    0x44 => {
+                        let entity_id = VarInt::deserialize(input).await?;
+                        let size = length as usize
+                            - VarInt(packet_id).size() as usize
+                            - entity_id.size() as usize;
+                        let mut metadata = vec![0u8; size];
+                        input.read(&mut metadata).await?;
+
+                        Ok(Self::EntityMetadata(entity_id, metadata))
+                    }
+                    0x55 => Ok(Self::CollectItem(
+                        VarInt::deserialize(input).await?,
+                        VarInt::deserialize(input).await?,
+                        VarInt::deserialize(input).await?,
+                    )),
+                    0x48 => Ok(Self::SetExperience(
+                        f32::deserialize(input).await?,
+                        VarInt::deserialize(input).await?,
+                        VarInt::deserialize(input).await?,
+                    )),
+                    0x0E => Ok(Self::ChatMessage(
+                        MString::deserialize(input).await?,
+                        u8::deserialize(input).await?,
+                        u128::deserialize(input).await?,
+                    )),
                     _ => Ok(Self::Unknown(VarInt(packet_id))),
                 }
             }
@@ -298,3 +595,35 @@ impl ClientBound {
         result
     }
 }
+
+// the players in an "add player" player info packet, or none for the other actions
+async fn read_added_players(input: &mut Cursor<Vec<u8>>) -> io::Result<Vec<(u128, MString)>> {
+    let action = VarInt::deserialize(input).await?;
+    if action.0 != 0 {
+        return Ok(Vec::new());
+    }
+    let count = VarInt::deserialize(input).await?;
+    let mut players = Vec::with_capacity(count.0.max(0) as usize);
+    for _ in 0..count.0 {
+        let uuid = u128::deserialize(input).await?;
+        let name = MString::deserialize(input).await?;
+        // skin properties: name, value and an optional signature
+        let properties = VarInt::deserialize(input).await?;
+        for _ in 0..properties.0 {
+            MString::deserialize(input).await?;
+            MString::deserialize(input).await?;
+            if bool::deserialize(input).await? {
+                MString::deserialize(input).await?;
+            }
+        }
+        // gamemode, ping
+        VarInt::deserialize(input).await?;
+        VarInt::deserialize(input).await?;
+        // optional display name
+        if bool::deserialize(input).await? {
+            MString::deserialize(input).await?;
+        }
+        players.push((uuid, name));
+    }
+    Ok(players)
+}
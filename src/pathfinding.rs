@@ -0,0 +1,256 @@
+// finds a way to walk somewhere using A* over the blocks of the world
+// positions are the blocks that the feet are in
+//
+// the bot can walk to the 4 neighbouring blocks, jump one block up and drop down a few
+// blocks, as long as it doesn't take fall damage or walk into lava
+
+use crate::blocks;
+use crate::world::World;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+// stop searching after this many blocks, so that unreachable goals don't freeze the bot
+const MAX_VISITED: usize = 20_000;
+// falling more than 3 blocks hurts
+const MAX_FALL: i32 = 3;
+
+// costs of the moves, multiplied by 10 to keep them integers
+const WALK_COST: u32 = 10;
+const JUMP_COST: u32 = 15;
+const FALL_COST: u32 = 2; // per block fallen, in addition to walking off the edge
+
+const DIRECTIONS: [(i32, i32); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+// the blocks to walk through, in order
+pub type Path = Vec<(i32, i32, i32)>;
+
+// returns the blocks to walk through, not including the start
+// if the goal can't be reached (for example because its chunk isn't loaded yet), the path
+// leads to the closest block that can be, so the search can be repeated from there later
+// None if not even that is possible
+pub fn find_path(world: &World, start: (i32, i32, i32), goal: (i32, i32, i32)) -> Option<Path> {
+    let mut open = BinaryHeap::new();
+    let mut came_from = HashMap::new();
+    let mut costs = HashMap::new();
+    let mut closest = (heuristic(start, goal), start);
+
+    open.push(Reverse((heuristic(start, goal), start)));
+    costs.insert(start, 0);
+
+    while let Some(Reverse((_, current))) = open.pop() {
+        if current == goal {
+            closest = (0, current);
+            break;
+        }
+        if costs.len() > MAX_VISITED {
+            break;
+        }
+        if heuristic(current, goal) < closest.0 {
+            closest = (heuristic(current, goal), current);
+        }
+
+        let cost = costs[&current];
+        for (neighbour, move_cost) in neighbours(world, current) {
+            let new_cost = cost + move_cost;
+            if costs.get(&neighbour).is_none_or(|&old| new_cost < old) {
+                costs.insert(neighbour, new_cost);
+                came_from.insert(neighbour, current);
+                open.push(Reverse((new_cost + heuristic(neighbour, goal), neighbour)));
+            }
+        }
+    }
+
+    if closest.1 == start {
+        return None;
+    }
+
+    // walk back from the end to build the path
+    let mut path = vec![closest.1];
+    while let Some(&previous) = came_from.get(path.last().unwrap()) {
+        if previous == start {
+            break;
+        }
+        path.push(previous);
+    }
+    path.reverse();
+
+    Some(path)
+}
+
+// whether the bot can stand with its feet in the given block
+pub fn can_stand(world: &World, x: i32, y: i32, z: i32) -> bool {
+    let safe = |y| match world.block(x, y, z) {
+        Some(state) => blocks::is_passable(state) && !blocks::is_lava(state),
+        None => false,
+    };
+    let supported = match (world.block(x, y, z), world.block(x, y - 1, z)) {
+        // floating in water is fine too
+        (Some(feet), _) if blocks::is_water(feet) => true,
+        (_, Some(below)) => !blocks::is_passable(below),
+        _ => false,
+    };
+
+    safe(y) && safe(y + 1) && supported
+}
+
+// the blocks that can be reached from the given one in one move, with the cost of the move
+fn neighbours(world: &World, (x, y, z): (i32, i32, i32)) -> Vec<((i32, i32, i32), u32)> {
+    let passable = |x, y, z| match world.block(x, y, z) {
+        Some(state) => blocks::is_passable(state) && !blocks::is_lava(state),
+        None => false,
+    };
+
+    let mut result = Vec::new();
+    for &(dx, dz) in &DIRECTIONS {
+        let (nx, nz) = (x + dx, z + dz);
+
+        if can_stand(world, nx, y, nz) {
+            result.push(((nx, y, nz), WALK_COST));
+        } else if passable(x, y + 2, z) && can_stand(world, nx, y + 1, nz) {
+            // there must be room above the head to jump
+            result.push(((nx, y + 1, nz), JUMP_COST));
+        } else if passable(nx, y, nz) && passable(nx, y + 1, nz) {
+            // walk off the edge and fall down
+            for fall in 1..=MAX_FALL {
+                if can_stand(world, nx, y - fall, nz) {
+                    result.push(((nx, y - fall, nz), WALK_COST + FALL_COST * fall as u32));
+                    break;
+                }
+                if !passable(nx, y - fall, nz) {
+                    break;
+                }
+            }
+        }
+    }
+
+    result
+}
+
+// never more than the real cost, since every move goes 1 block sideways and at most 1 up
+fn heuristic(from: (i32, i32, i32), to: (i32, i32, i32)) -> u32 {
+    let horizontal = (from.0 - to.0).abs() + (from.2 - to.2).abs();
+    let up = (to.1 - from.1).max(0);
+
+    horizontal.max(up) as u32 * WALK_COST
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::Chunk;
+
+    const STONE: u32 = 1;
+    const LAVA: u32 = 50;
+
+    // one chunk of air with a stone floor at y 63, so the bot stands at y 64
+    async fn flat_world() -> World {
+        let mut world = World::default();
+        world.load_chunk(0, 0, true, Chunk::parse(0, Vec::new()).await.unwrap());
+        for x in 0..16 {
+            for z in 0..16 {
+                world.set_block(x, 63, z, STONE);
+            }
+        }
+        world
+    }
+
+    // every step goes to a neighbouring block
+    fn assert_walkable(start: (i32, i32, i32), path: &[(i32, i32, i32)]) {
+        let mut previous = start;
+        for &block in path {
+            let sideways = (block.0 - previous.0).abs() + (block.2 - previous.2).abs();
+            assert_eq!(sideways, 1, "{:?} -> {:?}", previous, block);
+            assert!(block.1 - previous.1 <= 1, "{:?} -> {:?}", previous, block);
+            previous = block;
+        }
+    }
+
+    #[tokio::test]
+    async fn straight_path() {
+        let world = flat_world().await;
+        let path = find_path(&world, (0, 64, 0), (5, 64, 0)).unwrap();
+        assert_eq!(
+            path,
+            vec![(1, 64, 0), (2, 64, 0), (3, 64, 0), (4, 64, 0), (5, 64, 0)]
+        );
+    }
+
+    #[tokio::test]
+    async fn jumps_up_one_block() {
+        let mut world = flat_world().await;
+        for x in 3..16 {
+            for z in 0..16 {
+                world.set_block(x, 64, z, STONE);
+            }
+        }
+        let path = find_path(&world, (0, 64, 0), (5, 65, 0)).unwrap();
+        assert_walkable((0, 64, 0), &path);
+        assert_eq!(path.last(), Some(&(5, 65, 0)));
+
+        // two blocks is too high
+        for x in 3..16 {
+            for z in 0..16 {
+                world.set_block(x, 65, z, STONE);
+            }
+        }
+        let path = find_path(&world, (0, 64, 0), (5, 66, 0)).unwrap();
+        assert_ne!(path.last(), Some(&(5, 66, 0)));
+    }
+
+    #[tokio::test]
+    async fn drops_only_as_far_as_is_safe() {
+        let mut world = flat_world().await;
+        // a pillar 3 blocks above the floor
+        for y in 64..67 {
+            world.set_block(0, y, 0, STONE);
+        }
+        let path = find_path(&world, (0, 67, 0), (5, 64, 0)).unwrap();
+        assert_eq!(path.first(), Some(&(1, 64, 0)));
+        assert_eq!(path.last(), Some(&(5, 64, 0)));
+
+        // one more block and the fall would hurt
+        world.set_block(0, 67, 0, STONE);
+        assert_eq!(MAX_FALL, 3);
+        assert_eq!(find_path(&world, (0, 68, 0), (5, 64, 0)), None);
+    }
+
+    #[tokio::test]
+    async fn avoids_lava() {
+        let mut world = flat_world().await;
+        for z in 0..15 {
+            world.set_block(3, 64, z, LAVA);
+        }
+        let path = find_path(&world, (0, 64, 0), (5, 64, 0)).unwrap();
+        assert_walkable((0, 64, 0), &path);
+        assert!(path.contains(&(3, 64, 15)));
+        assert_eq!(path.last(), Some(&(5, 64, 0)));
+    }
+
+    #[tokio::test]
+    async fn no_path() {
+        let mut world = flat_world().await;
+        // walls 2 blocks high around the goal
+        for (x, z) in [
+            (7, 7),
+            (8, 7),
+            (9, 7),
+            (7, 8),
+            (9, 8),
+            (7, 9),
+            (8, 9),
+            (9, 9),
+        ]
+        .iter()
+        {
+            world.set_block(*x, 64, *z, STONE);
+            world.set_block(*x, 65, *z, STONE);
+        }
+        // it gets as close as it can
+        let path = find_path(&world, (0, 64, 8), (8, 64, 8)).unwrap();
+        assert_walkable((0, 64, 8), &path);
+        assert_ne!(path.last(), Some(&(8, 64, 8)));
+
+        // and can't get out from there
+        assert_eq!(find_path(&world, (8, 64, 8), (0, 64, 8)), None);
+    }
+}
@@ -0,0 +1,285 @@
+// a simplified version of vanilla's player movement, enough to fall, land, float and
+// not be seen as flying by the server
+// all numbers are per tick (1/20 s), like in vanilla
+
+use crate::blocks;
+use crate::world::World;
+
+const WIDTH: f64 = 0.6;
+const HEIGHT: f64 = 1.8;
+// used when comparing positions so that touching blocks don't count as overlapping
+const EPSILON: f64 = 1e-7;
+// the drag on the ground, most blocks have a friction of 0.6
+const GROUND_DRAG: f64 = 0.6 * 0.91;
+
+// the furthest the bot will try to walk back to its spot
+pub const MAX_WALK_DISTANCE: f64 = 16.0;
+
+// moves the player by its velocity, stopping at solid blocks, then applies gravity and drag
+pub fn tick(
+    world: &World,
+    pos: &mut (f64, f64, f64),
+    velocity: &mut (f64, f64, f64),
+    on_ground: &mut bool,
+) {
+    // don't fall into chunks that haven't been received yet
+    if world
+        .block(
+            pos.0.floor() as i32,
+            pos.1.floor() as i32,
+            pos.2.floor() as i32,
+        )
+        .is_none()
+    {
+        return;
+    }
+
+    let (mut min, mut max) = bounding_box(*pos);
+    let wanted = [velocity.0, velocity.1, velocity.2];
+    let mut moved = [0.0; 3];
+    // vertical movement first, like vanilla
+    for &axis in &[1, 0, 2] {
+        moved[axis] = clip(world, min, max, axis, wanted[axis]);
+        min[axis] += moved[axis];
+        max[axis] += moved[axis];
+    }
+    *pos = (pos.0 + moved[0], pos.1 + moved[1], pos.2 + moved[2]);
+    *on_ground = wanted[1] < 0.0 && moved[1] != wanted[1];
+
+    // stop moving in the directions that hit something
+    if moved[0] != wanted[0] {
+        velocity.0 = 0.0;
+    }
+    if moved[1] != wanted[1] {
+        velocity.1 = 0.0;
+    }
+    if moved[2] != wanted[2] {
+        velocity.2 = 0.0;
+    }
+
+    let liquid = world.block(
+        pos.0.floor() as i32,
+        (pos.1 + 0.4).floor() as i32,
+        pos.2.floor() as i32,
+    );
+    match liquid {
+        Some(state) if blocks::is_water(state) => {
+            *velocity = (velocity.0 * 0.8, velocity.1 * 0.8 - 0.02, velocity.2 * 0.8);
+        }
+        Some(state) if blocks::is_lava(state) => {
+            *velocity = (velocity.0 * 0.5, velocity.1 * 0.5 - 0.02, velocity.2 * 0.5);
+        }
+        _ => {
+            let drag = if *on_ground { GROUND_DRAG } else { 0.91 };
+            *velocity = (
+                velocity.0 * drag,
+                (velocity.1 - 0.08) * 0.98,
+                velocity.2 * drag,
+            );
+        }
+    }
+
+    // vanilla ignores tiny velocities too
+    let ignore_tiny = |v: f64| if v.abs() < 0.003 { 0.0 } else { v };
+    *velocity = (
+        ignore_tiny(velocity.0),
+        ignore_tiny(velocity.1),
+        ignore_tiny(velocity.2),
+    );
+}
+
+// accelerates towards the target like a walking player, slowing down in time to stop there
+pub fn walk_towards(
+    pos: (f64, f64, f64),
+    target: (f64, f64, f64),
+    velocity: &mut (f64, f64, f64),
+    on_ground: bool,
+) {
+    let (dx, dz) = (target.0 - pos.0, target.2 - pos.2);
+    let distance = horizontal_distance(pos, target);
+    if distance == 0.0 {
+        return;
+    }
+    let direction = (dx / distance, dz / distance);
+
+    // how far the current speed would carry us without walking anymore
+    let speed = velocity.0 * direction.0 + velocity.2 * direction.1;
+    let stopping_distance = speed * GROUND_DRAG / (1.0 - GROUND_DRAG);
+    if stopping_distance >= distance {
+        return;
+    }
+
+    // vanilla's walking acceleration, much weaker in the air
+    let acceleration = if on_ground { 0.1 } else { 0.02 };
+    velocity.0 += direction.0 * acceleration;
+    velocity.2 += direction.1 * acceleration;
+}
+
+pub fn horizontal_distance(a: (f64, f64, f64), b: (f64, f64, f64)) -> f64 {
+    ((a.0 - b.0).powi(2) + (a.2 - b.2).powi(2)).sqrt()
+}
+
+fn bounding_box(pos: (f64, f64, f64)) -> ([f64; 3], [f64; 3]) {
+    (
+        [pos.0 - WIDTH / 2.0, pos.1, pos.2 - WIDTH / 2.0],
+        [pos.0 + WIDTH / 2.0, pos.1 + HEIGHT, pos.2 + WIDTH / 2.0],
+    )
+}
+
+// how far the bounding box can move along the axis before hitting a block's collision box
+fn clip(world: &World, min: [f64; 3], max: [f64; 3], axis: usize, distance: f64) -> f64 {
+    if distance == 0.0 {
+        return 0.0;
+    }
+
+    // the area that the bounding box sweeps through
+    let mut from = min;
+    let mut to = max;
+    if distance > 0.0 {
+        to[axis] += distance;
+    } else {
+        from[axis] += distance;
+    }
+
+    let mut distance = distance;
+    for x in (from[0] + EPSILON).floor() as i32..=(to[0] - EPSILON).floor() as i32 {
+        for y in (from[1] + EPSILON).floor() as i32..=(to[1] - EPSILON).floor() as i32 {
+            for z in (from[2] + EPSILON).floor() as i32..=(to[2] - EPSILON).floor() as i32 {
+                let boxes = match world.block(x, y, z) {
+                    Some(state) => blocks::collision_boxes(state),
+                    // unloaded chunks are like walls
+                    None => vec![([0.0; 3], [1.0; 3])],
+                };
+
+                let corner = [x as f64, y as f64, z as f64];
+                for (block_min, block_max) in boxes {
+                    let block_min = [
+                        corner[0] + block_min[0],
+                        corner[1] + block_min[1],
+                        corner[2] + block_min[2],
+                    ];
+                    let block_max = [
+                        corner[0] + block_max[0],
+                        corner[1] + block_max[1],
+                        corner[2] + block_max[2],
+                    ];
+                    // boxes beside the bounding box can't be hit
+                    let beside = (0..3).any(|other| {
+                        other != axis
+                            && (block_min[other] >= max[other] - EPSILON
+                                || block_max[other] <= min[other] + EPSILON)
+                    });
+                    if beside {
+                        continue;
+                    }
+
+                    if distance > 0.0 && block_min[axis] >= max[axis] - EPSILON {
+                        distance = distance.min(block_min[axis] - max[axis]);
+                    } else if distance < 0.0 && block_max[axis] <= min[axis] + EPSILON {
+                        distance = distance.max(block_max[axis] - min[axis]);
+                    } else if distance < 0.0 && axis == 1 && block_min[1] <= min[1] + EPSILON {
+                        // already standing inside it, it's still the floor
+                        distance = 0.0;
+                    }
+                }
+            }
+        }
+    }
+
+    distance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::Chunk;
+
+    const STONE: u32 = 1;
+    const WATER: u32 = 34;
+    const STONE_SLAB_BOTTOM: u32 = 8343;
+
+    // a single chunk of air with the given blocks
+    async fn world(blocks: &[(i32, i32, i32, u32)]) -> World {
+        let mut world = World::default();
+        world.load_chunk(0, 0, true, Chunk::parse(0, Vec::new()).await.unwrap());
+        for &(x, y, z, state) in blocks {
+            world.set_block(x, y, z, state);
+        }
+        world
+    }
+
+    // ticks for five seconds, returning whether the bot ended up on the ground
+    fn settle(world: &World, pos: &mut (f64, f64, f64)) -> bool {
+        let mut velocity = (0.0, 0.0, 0.0);
+        let mut on_ground = false;
+        for _ in 0..100 {
+            tick(world, pos, &mut velocity, &mut on_ground);
+        }
+        on_ground
+    }
+
+    #[tokio::test]
+    async fn stands_on_a_full_block() {
+        let world = world(&[(0, 63, 0, STONE)]).await;
+        let mut pos = (0.5, 64.0, 0.5);
+        assert!(settle(&world, &mut pos));
+        assert_eq!(pos, (0.5, 64.0, 0.5));
+    }
+
+    #[tokio::test]
+    async fn lands_on_a_slab() {
+        let world = world(&[(0, 64, 0, STONE_SLAB_BOTTOM)]).await;
+        let mut pos = (0.5, 66.0, 0.5);
+        assert!(settle(&world, &mut pos));
+        assert_eq!(pos, (0.5, 64.5, 0.5));
+    }
+
+    #[tokio::test]
+    async fn falls_through_an_air_gap() {
+        let world = world(&[(0, 60, 0, STONE)]).await;
+        let mut pos = (0.5, 64.0, 0.5);
+        assert!(settle(&world, &mut pos));
+        assert_eq!(pos, (0.5, 61.0, 0.5));
+    }
+
+    #[tokio::test]
+    async fn stops_in_a_block_it_is_already_in() {
+        let world = world(&[(0, 64, 0, STONE)]).await;
+        let mut pos = (0.5, 64.2, 0.5);
+        assert!(settle(&world, &mut pos));
+        assert_eq!(pos, (0.5, 64.2, 0.5));
+    }
+
+    #[tokio::test]
+    async fn sinks_slowly_in_water() {
+        let world = world(&[
+            (0, 60, 0, STONE),
+            (0, 61, 0, WATER),
+            (0, 62, 0, WATER),
+            (0, 63, 0, WATER),
+            (0, 64, 0, WATER),
+        ])
+        .await;
+        let mut pos = (0.5, 64.0, 0.5);
+        let mut velocity = (0.0, 0.0, 0.0);
+        let mut on_ground = false;
+        for _ in 0..10 {
+            tick(&world, &mut pos, &mut velocity, &mut on_ground);
+        }
+        // drag keeps the speed under 0.1 blocks per tick, falling in air would be past 0.6
+        assert!(velocity.1 > -0.1 && velocity.1 < 0.0);
+        assert!(pos.1 > 63.0 && pos.1 < 64.0);
+        assert!(!on_ground);
+
+        assert!(settle(&world, &mut pos));
+        assert_eq!(pos.1, 61.0);
+    }
+
+    #[tokio::test]
+    async fn waits_for_unloaded_chunks() {
+        let world = World::default();
+        let mut pos = (0.5, 64.0, 0.5);
+        assert!(!settle(&world, &mut pos));
+        assert_eq!(pos, (0.5, 64.0, 0.5));
+    }
+}
@@ -0,0 +1,1840 @@
+// names of the things that statistics are counted for, by their registry IDs
+// the IDs are only valid for protocol 754 (1.16.5), other versions number them differently
+
+use crate::mobs::MOBS;
+
+pub const CATEGORIES: [&str; 9] = [
+    "Mined",
+    "Crafted",
+    "Used",
+    "Broken",
+    "Picked up",
+    "Dropped",
+    "Killed",
+    "Killed by",
+    "Custom",
+];
+
+// both in registry order, so the ID is the index
+const BLOCKS: [&str; 763] = [
+    "Air",
+    "Stone",
+    "Granite",
+    "Polished Granite",
+    "Diorite",
+    "Polished Diorite",
+    "Andesite",
+    "Polished Andesite",
+    "Grass Block",
+    "Dirt",
+    "Coarse Dirt",
+    "Podzol",
+    "Cobblestone",
+    "Oak Planks",
+    "Spruce Planks",
+    "Birch Planks",
+    "Jungle Planks",
+    "Acacia Planks",
+    "Dark Oak Planks",
+    "Oak Sapling",
+    "Spruce Sapling",
+    "Birch Sapling",
+    "Jungle Sapling",
+    "Acacia Sapling",
+    "Dark Oak Sapling",
+    "Bedrock",
+    "Water",
+    "Lava",
+    "Sand",
+    "Red Sand",
+    "Gravel",
+    "Gold Ore",
+    "Iron Ore",
+    "Coal Ore",
+    "Nether Gold Ore",
+    "Oak Log",
+    "Spruce Log",
+    "Birch Log",
+    "Jungle Log",
+    "Acacia Log",
+    "Dark Oak Log",
+    "Stripped Spruce Log",
+    "Stripped Birch Log",
+    "Stripped Jungle Log",
+    "Stripped Acacia Log",
+    "Stripped Dark Oak Log",
+    "Stripped Oak Log",
+    "Oak Wood",
+    "Spruce Wood",
+    "Birch Wood",
+    "Jungle Wood",
+    "Acacia Wood",
+    "Dark Oak Wood",
+    "Stripped Oak Wood",
+    "Stripped Spruce Wood",
+    "Stripped Birch Wood",
+    "Stripped Jungle Wood",
+    "Stripped Acacia Wood",
+    "Stripped Dark Oak Wood",
+    "Oak Leaves",
+    "Spruce Leaves",
+    "Birch Leaves",
+    "Jungle Leaves",
+    "Acacia Leaves",
+    "Dark Oak Leaves",
+    "Sponge",
+    "Wet Sponge",
+    "Glass",
+    "Lapis Lazuli Ore",
+    "Lapis Lazuli Block",
+    "Dispenser",
+    "Sandstone",
+    "Chiseled Sandstone",
+    "Cut Sandstone",
+    "Note Block",
+    "White Bed",
+    "Orange Bed",
+    "Magenta Bed",
+    "Light Blue Bed",
+    "Yellow Bed",
+    "Lime Bed",
+    "Pink Bed",
+    "Gray Bed",
+    "Light Gray Bed",
+    "Cyan Bed",
+    "Purple Bed",
+    "Blue Bed",
+    "Brown Bed",
+    "Green Bed",
+    "Red Bed",
+    "Black Bed",
+    "Powered Rail",
+    "Detector Rail",
+    "Sticky Piston",
+    "Cobweb",
+    "Grass",
+    "Fern",
+    "Dead Bush",
+    "Seagrass",
+    "Tall Seagrass",
+    "Piston",
+    "Piston Head",
+    "White Wool",
+    "Orange Wool",
+    "Magenta Wool",
+    "Light Blue Wool",
+    "Yellow Wool",
+    "Lime Wool",
+    "Pink Wool",
+    "Gray Wool",
+    "Light Gray Wool",
+    "Cyan Wool",
+    "Purple Wool",
+    "Blue Wool",
+    "Brown Wool",
+    "Green Wool",
+    "Red Wool",
+    "Black Wool",
+    "Moving Piston",
+    "Dandelion",
+    "Poppy",
+    "Blue Orchid",
+    "Allium",
+    "Azure Bluet",
+    "Red Tulip",
+    "Orange Tulip",
+    "White Tulip",
+    "Pink Tulip",
+    "Oxeye Daisy",
+    "Cornflower",
+    "Wither Rose",
+    "Lily of the Valley",
+    "Brown Mushroom",
+    "Red Mushroom",
+    "Block of Gold",
+    "Block of Iron",
+    "Bricks",
+    "TNT",
+    "Bookshelf",
+    "Mossy Cobblestone",
+    "Obsidian",
+    "Torch",
+    "Wall Torch",
+    "Fire",
+    "Soul Fire",
+    "Spawner",
+    "Oak Stairs",
+    "Chest",
+    "Redstone Wire",
+    "Diamond Ore",
+    "Block of Diamond",
+    "Crafting Table",
+    "Wheat Crops",
+    "Farmland",
+    "Furnace",
+    "Oak Sign",
+    "Spruce Sign",
+    "Birch Sign",
+    "Acacia Sign",
+    "Jungle Sign",
+    "Dark Oak Sign",
+    "Oak Door",
+    "Ladder",
+    "Rail",
+    "Cobblestone Stairs",
+    "Oak Wall Sign",
+    "Spruce Wall Sign",
+    "Birch Wall Sign",
+    "Acacia Wall Sign",
+    "Jungle Wall Sign",
+    "Dark Oak Wall Sign",
+    "Lever",
+    "Stone Pressure Plate",
+    "Iron Door",
+    "Oak Pressure Plate",
+    "Spruce Pressure Plate",
+    "Birch Pressure Plate",
+    "Jungle Pressure Plate",
+    "Acacia Pressure Plate",
+    "Dark Oak Pressure Plate",
+    "Redstone Ore",
+    "Redstone Torch",
+    "Redstone Wall Torch",
+    "Stone Button",
+    "Snow",
+    "Ice",
+    "Snow Block",
+    "Cactus",
+    "Clay",
+    "Sugar Cane",
+    "Jukebox",
+    "Oak Fence",
+    "Pumpkin",
+    "Netherrack",
+    "Soul Sand",
+    "Soul Soil",
+    "Basalt",
+    "Polished Basalt",
+    "Soul Torch",
+    "Soul Wall Torch",
+    "Glowstone",
+    "Nether Portal",
+    "Carved Pumpkin",
+    "Jack o'Lantern",
+    "Cake",
+    "Repeater",
+    "White Stained Glass",
+    "Orange Stained Glass",
+    "Magenta Stained Glass",
+    "Light Blue Stained Glass",
+    "Yellow Stained Glass",
+    "Lime Stained Glass",
+    "Pink Stained Glass",
+    "Gray Stained Glass",
+    "Light Gray Stained Glass",
+    "Cyan Stained Glass",
+    "Purple Stained Glass",
+    "Blue Stained Glass",
+    "Brown Stained Glass",
+    "Green Stained Glass",
+    "Red Stained Glass",
+    "Black Stained Glass",
+    "Oak Trapdoor",
+    "Spruce Trapdoor",
+    "Birch Trapdoor",
+    "Jungle Trapdoor",
+    "Acacia Trapdoor",
+    "Dark Oak Trapdoor",
+    "Stone Bricks",
+    "Mossy Stone Bricks",
+    "Cracked Stone Bricks",
+    "Chiseled Stone Bricks",
+    "Infested Stone",
+    "Infested Cobblestone",
+    "Infested Stone Bricks",
+    "Infested Mossy Stone Bricks",
+    "Infested Cracked Stone Bricks",
+    "Infested Chiseled Stone Bricks",
+    "Brown Mushroom Block",
+    "Red Mushroom Block",
+    "Mushroom Stem",
+    "Iron Bars",
+    "Chain",
+    "Glass Pane",
+    "Melon",
+    "Attached Pumpkin Stem",
+    "Attached Melon Stem",
+    "Pumpkin Stem",
+    "Melon Stem",
+    "Vine",
+    "Oak Fence Gate",
+    "Brick Stairs",
+    "Stone Brick Stairs",
+    "Mycelium",
+    "Lily Pad",
+    "Nether Bricks",
+    "Nether Brick Fence",
+    "Nether Brick Stairs",
+    "Nether Wart",
+    "Enchanting Table",
+    "Brewing Stand",
+    "Cauldron",
+    "End Portal",
+    "End Portal Frame",
+    "End Stone",
+    "Dragon Egg",
+    "Redstone Lamp",
+    "Cocoa",
+    "Sandstone Stairs",
+    "Emerald Ore",
+    "Ender Chest",
+    "Tripwire Hook",
+    "Tripwire",
+    "Block of Emerald",
+    "Spruce Stairs",
+    "Birch Stairs",
+    "Jungle Stairs",
+    "Command Block",
+    "Beacon",
+    "Cobblestone Wall",
+    "Mossy Cobblestone Wall",
+    "Flower Pot",
+    "Potted Oak Sapling",
+    "Potted Spruce Sapling",
+    "Potted Birch Sapling",
+    "Potted Jungle Sapling",
+    "Potted Acacia Sapling",
+    "Potted Dark Oak Sapling",
+    "Potted Fern",
+    "Potted Dandelion",
+    "Potted Poppy",
+    "Potted Blue Orchid",
+    "Potted Allium",
+    "Potted Azure Bluet",
+    "Potted Red Tulip",
+    "Potted Orange Tulip",
+    "Potted White Tulip",
+    "Potted Pink Tulip",
+    "Potted Oxeye Daisy",
+    "Potted Cornflower",
+    "Potted Lily of the Valley",
+    "Potted Wither Rose",
+    "Potted Red Mushroom",
+    "Potted Brown Mushroom",
+    "Potted Dead Bush",
+    "Potted Cactus",
+    "Carrots",
+    "Potatoes",
+    "Oak Button",
+    "Spruce Button",
+    "Birch Button",
+    "Jungle Button",
+    "Acacia Button",
+    "Dark Oak Button",
+    "Skeleton Skull",
+    "Skeleton Wall Skull",
+    "Wither Skeleton Skull",
+    "Wither Skeleton Wall Skull",
+    "Zombie Head",
+    "Zombie Wall Head",
+    "Player Head",
+    "Player Wall Head",
+    "Creeper Head",
+    "Creeper Wall Head",
+    "Dragon Head",
+    "Dragon Wall Head",
+    "Anvil",
+    "Chipped Anvil",
+    "Damaged Anvil",
+    "Trapped Chest",
+    "Light Weighted Pressure Plate",
+    "Heavy Weighted Pressure Plate",
+    "Comparator",
+    "Daylight Detector",
+    "Block of Redstone",
+    "Nether Quartz Ore",
+    "Hopper",
+    "Block of Quartz",
+    "Chiseled Quartz Block",
+    "Quartz Pillar",
+    "Quartz Stairs",
+    "Activator Rail",
+    "Dropper",
+    "White Terracotta",
+    "Orange Terracotta",
+    "Magenta Terracotta",
+    "Light Blue Terracotta",
+    "Yellow Terracotta",
+    "Lime Terracotta",
+    "Pink Terracotta",
+    "Gray Terracotta",
+    "Light Gray Terracotta",
+    "Cyan Terracotta",
+    "Purple Terracotta",
+    "Blue Terracotta",
+    "Brown Terracotta",
+    "Green Terracotta",
+    "Red Terracotta",
+    "Black Terracotta",
+    "White Stained Glass Pane",
+    "Orange Stained Glass Pane",
+    "Magenta Stained Glass Pane",
+    "Light Blue Stained Glass Pane",
+    "Yellow Stained Glass Pane",
+    "Lime Stained Glass Pane",
+    "Pink Stained Glass Pane",
+    "Gray Stained Glass Pane",
+    "Light Gray Stained Glass Pane",
+    "Cyan Stained Glass Pane",
+    "Purple Stained Glass Pane",
+    "Blue Stained Glass Pane",
+    "Brown Stained Glass Pane",
+    "Green Stained Glass Pane",
+    "Red Stained Glass Pane",
+    "Black Stained Glass Pane",
+    "Acacia Stairs",
+    "Dark Oak Stairs",
+    "Slime Block",
+    "Barrier",
+    "Iron Trapdoor",
+    "Prismarine",
+    "Prismarine Bricks",
+    "Dark Prismarine",
+    "Prismarine Stairs",
+    "Prismarine Brick Stairs",
+    "Dark Prismarine Stairs",
+    "Prismarine Slab",
+    "Prismarine Brick Slab",
+    "Dark Prismarine Slab",
+    "Sea Lantern",
+    "Hay Bale",
+    "White Carpet",
+    "Orange Carpet",
+    "Magenta Carpet",
+    "Light Blue Carpet",
+    "Yellow Carpet",
+    "Lime Carpet",
+    "Pink Carpet",
+    "Gray Carpet",
+    "Light Gray Carpet",
+    "Cyan Carpet",
+    "Purple Carpet",
+    "Blue Carpet",
+    "Brown Carpet",
+    "Green Carpet",
+    "Red Carpet",
+    "Black Carpet",
+    "Terracotta",
+    "Block of Coal",
+    "Packed Ice",
+    "Sunflower",
+    "Lilac",
+    "Rose Bush",
+    "Peony",
+    "Tall Grass",
+    "Large Fern",
+    "White Banner",
+    "Orange Banner",
+    "Magenta Banner",
+    "Light Blue Banner",
+    "Yellow Banner",
+    "Lime Banner",
+    "Pink Banner",
+    "Gray Banner",
+    "Light Gray Banner",
+    "Cyan Banner",
+    "Purple Banner",
+    "Blue Banner",
+    "Brown Banner",
+    "Green Banner",
+    "Red Banner",
+    "Black Banner",
+    "White Wall Banner",
+    "Orange Wall Banner",
+    "Magenta Wall Banner",
+    "Light Blue Wall Banner",
+    "Yellow Wall Banner",
+    "Lime Wall Banner",
+    "Pink Wall Banner",
+    "Gray Wall Banner",
+    "Light Gray Wall Banner",
+    "Cyan Wall Banner",
+    "Purple Wall Banner",
+    "Blue Wall Banner",
+    "Brown Wall Banner",
+    "Green Wall Banner",
+    "Red Wall Banner",
+    "Black Wall Banner",
+    "Red Sandstone",
+    "Chiseled Red Sandstone",
+    "Cut Red Sandstone",
+    "Red Sandstone Stairs",
+    "Oak Slab",
+    "Spruce Slab",
+    "Birch Slab",
+    "Jungle Slab",
+    "Acacia Slab",
+    "Dark Oak Slab",
+    "Stone Slab",
+    "Smooth Stone Slab",
+    "Sandstone Slab",
+    "Cut Sandstone Slab",
+    "Petrified Oak Slab",
+    "Cobblestone Slab",
+    "Brick Slab",
+    "Stone Brick Slab",
+    "Nether Brick Slab",
+    "Quartz Slab",
+    "Red Sandstone Slab",
+    "Cut Red Sandstone Slab",
+    "Purpur Slab",
+    "Smooth Stone",
+    "Smooth Sandstone",
+    "Smooth Quartz Block",
+    "Smooth Red Sandstone",
+    "Spruce Fence Gate",
+    "Birch Fence Gate",
+    "Jungle Fence Gate",
+    "Acacia Fence Gate",
+    "Dark Oak Fence Gate",
+    "Spruce Fence",
+    "Birch Fence",
+    "Jungle Fence",
+    "Acacia Fence",
+    "Dark Oak Fence",
+    "Spruce Door",
+    "Birch Door",
+    "Jungle Door",
+    "Acacia Door",
+    "Dark Oak Door",
+    "End Rod",
+    "Chorus Plant",
+    "Chorus Flower",
+    "Purpur Block",
+    "Purpur Pillar",
+    "Purpur Stairs",
+    "End Stone Bricks",
+    "Beetroots",
+    "Grass Path",
+    "End Gateway",
+    "Repeating Command Block",
+    "Chain Command Block",
+    "Frosted Ice",
+    "Magma Block",
+    "Nether Wart Block",
+    "Red Nether Bricks",
+    "Bone Block",
+    "Structure Void",
+    "Observer",
+    "Shulker Box",
+    "White Shulker Box",
+    "Orange Shulker Box",
+    "Magenta Shulker Box",
+    "Light Blue Shulker Box",
+    "Yellow Shulker Box",
+    "Lime Shulker Box",
+    "Pink Shulker Box",
+    "Gray Shulker Box",
+    "Light Gray Shulker Box",
+    "Cyan Shulker Box",
+    "Purple Shulker Box",
+    "Blue Shulker Box",
+    "Brown Shulker Box",
+    "Green Shulker Box",
+    "Red Shulker Box",
+    "Black Shulker Box",
+    "White Glazed Terracotta",
+    "Orange Glazed Terracotta",
+    "Magenta Glazed Terracotta",
+    "Light Blue Glazed Terracotta",
+    "Yellow Glazed Terracotta",
+    "Lime Glazed Terracotta",
+    "Pink Glazed Terracotta",
+    "Gray Glazed Terracotta",
+    "Light Gray Glazed Terracotta",
+    "Cyan Glazed Terracotta",
+    "Purple Glazed Terracotta",
+    "Blue Glazed Terracotta",
+    "Brown Glazed Terracotta",
+    "Green Glazed Terracotta",
+    "Red Glazed Terracotta",
+    "Black Glazed Terracotta",
+    "White Concrete",
+    "Orange Concrete",
+    "Magenta Concrete",
+    "Light Blue Concrete",
+    "Yellow Concrete",
+    "Lime Concrete",
+    "Pink Concrete",
+    "Gray Concrete",
+    "Light Gray Concrete",
+    "Cyan Concrete",
+    "Purple Concrete",
+    "Blue Concrete",
+    "Brown Concrete",
+    "Green Concrete",
+    "Red Concrete",
+    "Black Concrete",
+    "White Concrete Powder",
+    "Orange Concrete Powder",
+    "Magenta Concrete Powder",
+    "Light Blue Concrete Powder",
+    "Yellow Concrete Powder",
+    "Lime Concrete Powder",
+    "Pink Concrete Powder",
+    "Gray Concrete Powder",
+    "Light Gray Concrete Powder",
+    "Cyan Concrete Powder",
+    "Purple Concrete Powder",
+    "Blue Concrete Powder",
+    "Brown Concrete Powder",
+    "Green Concrete Powder",
+    "Red Concrete Powder",
+    "Black Concrete Powder",
+    "Kelp",
+    "Kelp Plant",
+    "Dried Kelp Block",
+    "Turtle Egg",
+    "Dead Tube Coral Block",
+    "Dead Brain Coral Block",
+    "Dead Bubble Coral Block",
+    "Dead Fire Coral Block",
+    "Dead Horn Coral Block",
+    "Tube Coral Block",
+    "Brain Coral Block",
+    "Bubble Coral Block",
+    "Fire Coral Block",
+    "Horn Coral Block",
+    "Dead Tube Coral",
+    "Dead Brain Coral",
+    "Dead Bubble Coral",
+    "Dead Fire Coral",
+    "Dead Horn Coral",
+    "Tube Coral",
+    "Brain Coral",
+    "Bubble Coral",
+    "Fire Coral",
+    "Horn Coral",
+    "Dead Tube Coral Fan",
+    "Dead Brain Coral Fan",
+    "Dead Bubble Coral Fan",
+    "Dead Fire Coral Fan",
+    "Dead Horn Coral Fan",
+    "Tube Coral Fan",
+    "Brain Coral Fan",
+    "Bubble Coral Fan",
+    "Fire Coral Fan",
+    "Horn Coral Fan",
+    "Dead Tube Coral Wall Fan",
+    "Dead Brain Coral Wall Fan",
+    "Dead Bubble Coral Wall Fan",
+    "Dead Fire Coral Wall Fan",
+    "Dead Horn Coral Wall Fan",
+    "Tube Coral Wall Fan",
+    "Brain Coral Wall Fan",
+    "Bubble Coral Wall Fan",
+    "Fire Coral Wall Fan",
+    "Horn Coral Wall Fan",
+    "Sea Pickle",
+    "Blue Ice",
+    "Conduit",
+    "Bamboo Shoot",
+    "Bamboo",
+    "Potted Bamboo",
+    "Void Air",
+    "Cave Air",
+    "Bubble Column",
+    "Polished Granite Stairs",
+    "Smooth Red Sandstone Stairs",
+    "Mossy Stone Brick Stairs",
+    "Polished Diorite Stairs",
+    "Mossy Cobblestone Stairs",
+    "End Stone Brick Stairs",
+    "Stone Stairs",
+    "Smooth Sandstone Stairs",
+    "Smooth Quartz Stairs",
+    "Granite Stairs",
+    "Andesite Stairs",
+    "Red Nether Brick Stairs",
+    "Polished Andesite Stairs",
+    "Diorite Stairs",
+    "Polished Granite Slab",
+    "Smooth Red Sandstone Slab",
+    "Mossy Stone Brick Slab",
+    "Polished Diorite Slab",
+    "Mossy Cobblestone Slab",
+    "End Stone Brick Slab",
+    "Smooth Sandstone Slab",
+    "Smooth Quartz Slab",
+    "Granite Slab",
+    "Andesite Slab",
+    "Red Nether Brick Slab",
+    "Polished Andesite Slab",
+    "Diorite Slab",
+    "Brick Wall",
+    "Prismarine Wall",
+    "Red Sandstone Wall",
+    "Mossy Stone Brick Wall",
+    "Granite Wall",
+    "Stone Brick Wall",
+    "Nether Brick Wall",
+    "Andesite Wall",
+    "Red Nether Brick Wall",
+    "Sandstone Wall",
+    "End Stone Brick Wall",
+    "Diorite Wall",
+    "Scaffolding",
+    "Loom",
+    "Barrel",
+    "Smoker",
+    "Blast Furnace",
+    "Cartography Table",
+    "Fletching Table",
+    "Grindstone",
+    "Lectern",
+    "Smithing Table",
+    "Stonecutter",
+    "Bell",
+    "Lantern",
+    "Soul Lantern",
+    "Campfire",
+    "Soul Campfire",
+    "Sweet Berry Bush",
+    "Warped Stem",
+    "Stripped Warped Stem",
+    "Warped Hyphae",
+    "Stripped Warped Hyphae",
+    "Warped Nylium",
+    "Warped Fungus",
+    "Warped Wart Block",
+    "Warped Roots",
+    "Nether Sprouts",
+    "Crimson Stem",
+    "Stripped Crimson Stem",
+    "Crimson Hyphae",
+    "Stripped Crimson Hyphae",
+    "Crimson Nylium",
+    "Crimson Fungus",
+    "Shroomlight",
+    "Weeping Vines",
+    "Weeping Vines Plant",
+    "Twisting Vines",
+    "Twisting Vines Plant",
+    "Crimson Roots",
+    "Crimson Planks",
+    "Warped Planks",
+    "Crimson Slab",
+    "Warped Slab",
+    "Crimson Pressure Plate",
+    "Warped Pressure Plate",
+    "Crimson Fence",
+    "Warped Fence",
+    "Crimson Trapdoor",
+    "Warped Trapdoor",
+    "Crimson Fence Gate",
+    "Warped Fence Gate",
+    "Crimson Stairs",
+    "Warped Stairs",
+    "Crimson Button",
+    "Warped Button",
+    "Crimson Door",
+    "Warped Door",
+    "Crimson Sign",
+    "Warped Sign",
+    "Crimson Wall Sign",
+    "Warped Wall Sign",
+    "Structure Block",
+    "Jigsaw",
+    "Composter",
+    "Target",
+    "Bee Nest",
+    "Beehive",
+    "Honey Block",
+    "Honeycomb Block",
+    "Block of Netherite",
+    "Ancient Debris",
+    "Crying Obsidian",
+    "Respawn Anchor",
+    "Potted Crimson Fungus",
+    "Potted Warped Fungus",
+    "Potted Crimson Roots",
+    "Potted Warped Roots",
+    "Lodestone",
+    "Blackstone",
+    "Blackstone Stairs",
+    "Blackstone Wall",
+    "Blackstone Slab",
+    "Polished Blackstone",
+    "Polished Blackstone Bricks",
+    "Cracked Polished Blackstone Bricks",
+    "Chiseled Polished Blackstone",
+    "Polished Blackstone Brick Slab",
+    "Polished Blackstone Brick Stairs",
+    "Polished Blackstone Brick Wall",
+    "Gilded Blackstone",
+    "Polished Blackstone Stairs",
+    "Polished Blackstone Slab",
+    "Polished Blackstone Pressure Plate",
+    "Polished Blackstone Button",
+    "Polished Blackstone Wall",
+    "Chiseled Nether Bricks",
+    "Cracked Nether Bricks",
+    "Quartz Bricks",
+];
+
+const ITEMS: [&str; 976] = [
+    "Air",
+    "Stone",
+    "Granite",
+    "Polished Granite",
+    "Diorite",
+    "Polished Diorite",
+    "Andesite",
+    "Polished Andesite",
+    "Grass Block",
+    "Dirt",
+    "Coarse Dirt",
+    "Podzol",
+    "Crimson Nylium",
+    "Warped Nylium",
+    "Cobblestone",
+    "Oak Planks",
+    "Spruce Planks",
+    "Birch Planks",
+    "Jungle Planks",
+    "Acacia Planks",
+    "Dark Oak Planks",
+    "Crimson Planks",
+    "Warped Planks",
+    "Oak Sapling",
+    "Spruce Sapling",
+    "Birch Sapling",
+    "Jungle Sapling",
+    "Acacia Sapling",
+    "Dark Oak Sapling",
+    "Bedrock",
+    "Sand",
+    "Red Sand",
+    "Gravel",
+    "Gold Ore",
+    "Iron Ore",
+    "Coal Ore",
+    "Nether Gold Ore",
+    "Oak Log",
+    "Spruce Log",
+    "Birch Log",
+    "Jungle Log",
+    "Acacia Log",
+    "Dark Oak Log",
+    "Crimson Stem",
+    "Warped Stem",
+    "Stripped Oak Log",
+    "Stripped Spruce Log",
+    "Stripped Birch Log",
+    "Stripped Jungle Log",
+    "Stripped Acacia Log",
+    "Stripped Dark Oak Log",
+    "Stripped Crimson Stem",
+    "Stripped Warped Stem",
+    "Stripped Oak Wood",
+    "Stripped Spruce Wood",
+    "Stripped Birch Wood",
+    "Stripped Jungle Wood",
+    "Stripped Acacia Wood",
+    "Stripped Dark Oak Wood",
+    "Stripped Crimson Hyphae",
+    "Stripped Warped Hyphae",
+    "Oak Wood",
+    "Spruce Wood",
+    "Birch Wood",
+    "Jungle Wood",
+    "Acacia Wood",
+    "Dark Oak Wood",
+    "Crimson Hyphae",
+    "Warped Hyphae",
+    "Oak Leaves",
+    "Spruce Leaves",
+    "Birch Leaves",
+    "Jungle Leaves",
+    "Acacia Leaves",
+    "Dark Oak Leaves",
+    "Sponge",
+    "Wet Sponge",
+    "Glass",
+    "Lapis Lazuli Ore",
+    "Lapis Lazuli Block",
+    "Dispenser",
+    "Sandstone",
+    "Chiseled Sandstone",
+    "Cut Sandstone",
+    "Note Block",
+    "Powered Rail",
+    "Detector Rail",
+    "Sticky Piston",
+    "Cobweb",
+    "Grass",
+    "Fern",
+    "Dead Bush",
+    "Seagrass",
+    "Sea Pickle",
+    "Piston",
+    "White Wool",
+    "Orange Wool",
+    "Magenta Wool",
+    "Light Blue Wool",
+    "Yellow Wool",
+    "Lime Wool",
+    "Pink Wool",
+    "Gray Wool",
+    "Light Gray Wool",
+    "Cyan Wool",
+    "Purple Wool",
+    "Blue Wool",
+    "Brown Wool",
+    "Green Wool",
+    "Red Wool",
+    "Black Wool",
+    "Dandelion",
+    "Poppy",
+    "Blue Orchid",
+    "Allium",
+    "Azure Bluet",
+    "Red Tulip",
+    "Orange Tulip",
+    "White Tulip",
+    "Pink Tulip",
+    "Oxeye Daisy",
+    "Cornflower",
+    "Lily of the Valley",
+    "Wither Rose",
+    "Brown Mushroom",
+    "Red Mushroom",
+    "Crimson Fungus",
+    "Warped Fungus",
+    "Crimson Roots",
+    "Warped Roots",
+    "Nether Sprouts",
+    "Weeping Vines",
+    "Twisting Vines",
+    "Sugar Cane",
+    "Kelp",
+    "Bamboo",
+    "Block of Gold",
+    "Block of Iron",
+    "Oak Slab",
+    "Spruce Slab",
+    "Birch Slab",
+    "Jungle Slab",
+    "Acacia Slab",
+    "Dark Oak Slab",
+    "Crimson Slab",
+    "Warped Slab",
+    "Stone Slab",
+    "Smooth Stone Slab",
+    "Sandstone Slab",
+    "Cut Sandstone Slab",
+    "Petrified Oak Slab",
+    "Cobblestone Slab",
+    "Brick Slab",
+    "Stone Brick Slab",
+    "Nether Brick Slab",
+    "Quartz Slab",
+    "Red Sandstone Slab",
+    "Cut Red Sandstone Slab",
+    "Purpur Slab",
+    "Prismarine Slab",
+    "Prismarine Brick Slab",
+    "Dark Prismarine Slab",
+    "Smooth Quartz Block",
+    "Smooth Red Sandstone",
+    "Smooth Sandstone",
+    "Smooth Stone",
+    "Bricks",
+    "TNT",
+    "Bookshelf",
+    "Mossy Cobblestone",
+    "Obsidian",
+    "Torch",
+    "End Rod",
+    "Chorus Plant",
+    "Chorus Flower",
+    "Purpur Block",
+    "Purpur Pillar",
+    "Purpur Stairs",
+    "Spawner",
+    "Oak Stairs",
+    "Chest",
+    "Diamond Ore",
+    "Block of Diamond",
+    "Crafting Table",
+    "Farmland",
+    "Furnace",
+    "Ladder",
+    "Rail",
+    "Cobblestone Stairs",
+    "Lever",
+    "Stone Pressure Plate",
+    "Oak Pressure Plate",
+    "Spruce Pressure Plate",
+    "Birch Pressure Plate",
+    "Jungle Pressure Plate",
+    "Acacia Pressure Plate",
+    "Dark Oak Pressure Plate",
+    "Crimson Pressure Plate",
+    "Warped Pressure Plate",
+    "Polished Blackstone Pressure Plate",
+    "Redstone Ore",
+    "Redstone Torch",
+    "Snow",
+    "Ice",
+    "Snow Block",
+    "Cactus",
+    "Clay",
+    "Jukebox",
+    "Oak Fence",
+    "Spruce Fence",
+    "Birch Fence",
+    "Jungle Fence",
+    "Acacia Fence",
+    "Dark Oak Fence",
+    "Crimson Fence",
+    "Warped Fence",
+    "Pumpkin",
+    "Carved Pumpkin",
+    "Netherrack",
+    "Soul Sand",
+    "Soul Soil",
+    "Basalt",
+    "Polished Basalt",
+    "Soul Torch",
+    "Glowstone",
+    "Jack o'Lantern",
+    "Oak Trapdoor",
+    "Spruce Trapdoor",
+    "Birch Trapdoor",
+    "Jungle Trapdoor",
+    "Acacia Trapdoor",
+    "Dark Oak Trapdoor",
+    "Crimson Trapdoor",
+    "Warped Trapdoor",
+    "Infested Stone",
+    "Infested Cobblestone",
+    "Infested Stone Bricks",
+    "Infested Mossy Stone Bricks",
+    "Infested Cracked Stone Bricks",
+    "Infested Chiseled Stone Bricks",
+    "Stone Bricks",
+    "Mossy Stone Bricks",
+    "Cracked Stone Bricks",
+    "Chiseled Stone Bricks",
+    "Brown Mushroom Block",
+    "Red Mushroom Block",
+    "Mushroom Stem",
+    "Iron Bars",
+    "Chain",
+    "Glass Pane",
+    "Melon",
+    "Vine",
+    "Oak Fence Gate",
+    "Spruce Fence Gate",
+    "Birch Fence Gate",
+    "Jungle Fence Gate",
+    "Acacia Fence Gate",
+    "Dark Oak Fence Gate",
+    "Crimson Fence Gate",
+    "Warped Fence Gate",
+    "Brick Stairs",
+    "Stone Brick Stairs",
+    "Mycelium",
+    "Lily Pad",
+    "Nether Bricks",
+    "Cracked Nether Bricks",
+    "Chiseled Nether Bricks",
+    "Nether Brick Fence",
+    "Nether Brick Stairs",
+    "Enchanting Table",
+    "End Portal Frame",
+    "End Stone",
+    "End Stone Bricks",
+    "Dragon Egg",
+    "Redstone Lamp",
+    "Sandstone Stairs",
+    "Emerald Ore",
+    "Ender Chest",
+    "Tripwire Hook",
+    "Block of Emerald",
+    "Spruce Stairs",
+    "Birch Stairs",
+    "Jungle Stairs",
+    "Crimson Stairs",
+    "Warped Stairs",
+    "Command Block",
+    "Beacon",
+    "Cobblestone Wall",
+    "Mossy Cobblestone Wall",
+    "Brick Wall",
+    "Prismarine Wall",
+    "Red Sandstone Wall",
+    "Mossy Stone Brick Wall",
+    "Granite Wall",
+    "Stone Brick Wall",
+    "Nether Brick Wall",
+    "Andesite Wall",
+    "Red Nether Brick Wall",
+    "Sandstone Wall",
+    "End Stone Brick Wall",
+    "Diorite Wall",
+    "Blackstone Wall",
+    "Polished Blackstone Wall",
+    "Polished Blackstone Brick Wall",
+    "Stone Button",
+    "Oak Button",
+    "Spruce Button",
+    "Birch Button",
+    "Jungle Button",
+    "Acacia Button",
+    "Dark Oak Button",
+    "Crimson Button",
+    "Warped Button",
+    "Polished Blackstone Button",
+    "Anvil",
+    "Chipped Anvil",
+    "Damaged Anvil",
+    "Trapped Chest",
+    "Light Weighted Pressure Plate",
+    "Heavy Weighted Pressure Plate",
+    "Daylight Detector",
+    "Block of Redstone",
+    "Nether Quartz Ore",
+    "Hopper",
+    "Chiseled Quartz Block",
+    "Block of Quartz",
+    "Quartz Bricks",
+    "Quartz Pillar",
+    "Quartz Stairs",
+    "Activator Rail",
+    "Dropper",
+    "White Terracotta",
+    "Orange Terracotta",
+    "Magenta Terracotta",
+    "Light Blue Terracotta",
+    "Yellow Terracotta",
+    "Lime Terracotta",
+    "Pink Terracotta",
+    "Gray Terracotta",
+    "Light Gray Terracotta",
+    "Cyan Terracotta",
+    "Purple Terracotta",
+    "Blue Terracotta",
+    "Brown Terracotta",
+    "Green Terracotta",
+    "Red Terracotta",
+    "Black Terracotta",
+    "Barrier",
+    "Iron Trapdoor",
+    "Hay Bale",
+    "White Carpet",
+    "Orange Carpet",
+    "Magenta Carpet",
+    "Light Blue Carpet",
+    "Yellow Carpet",
+    "Lime Carpet",
+    "Pink Carpet",
+    "Gray Carpet",
+    "Light Gray Carpet",
+    "Cyan Carpet",
+    "Purple Carpet",
+    "Blue Carpet",
+    "Brown Carpet",
+    "Green Carpet",
+    "Red Carpet",
+    "Black Carpet",
+    "Terracotta",
+    "Block of Coal",
+    "Packed Ice",
+    "Acacia Stairs",
+    "Dark Oak Stairs",
+    "Slime Block",
+    "Grass Path",
+    "Sunflower",
+    "Lilac",
+    "Rose Bush",
+    "Peony",
+    "Tall Grass",
+    "Large Fern",
+    "White Stained Glass",
+    "Orange Stained Glass",
+    "Magenta Stained Glass",
+    "Light Blue Stained Glass",
+    "Yellow Stained Glass",
+    "Lime Stained Glass",
+    "Pink Stained Glass",
+    "Gray Stained Glass",
+    "Light Gray Stained Glass",
+    "Cyan Stained Glass",
+    "Purple Stained Glass",
+    "Blue Stained Glass",
+    "Brown Stained Glass",
+    "Green Stained Glass",
+    "Red Stained Glass",
+    "Black Stained Glass",
+    "White Stained Glass Pane",
+    "Orange Stained Glass Pane",
+    "Magenta Stained Glass Pane",
+    "Light Blue Stained Glass Pane",
+    "Yellow Stained Glass Pane",
+    "Lime Stained Glass Pane",
+    "Pink Stained Glass Pane",
+    "Gray Stained Glass Pane",
+    "Light Gray Stained Glass Pane",
+    "Cyan Stained Glass Pane",
+    "Purple Stained Glass Pane",
+    "Blue Stained Glass Pane",
+    "Brown Stained Glass Pane",
+    "Green Stained Glass Pane",
+    "Red Stained Glass Pane",
+    "Black Stained Glass Pane",
+    "Prismarine",
+    "Prismarine Bricks",
+    "Dark Prismarine",
+    "Prismarine Stairs",
+    "Prismarine Brick Stairs",
+    "Dark Prismarine Stairs",
+    "Sea Lantern",
+    "Red Sandstone",
+    "Chiseled Red Sandstone",
+    "Cut Red Sandstone",
+    "Red Sandstone Stairs",
+    "Repeating Command Block",
+    "Chain Command Block",
+    "Magma Block",
+    "Nether Wart Block",
+    "Warped Wart Block",
+    "Red Nether Bricks",
+    "Bone Block",
+    "Structure Void",
+    "Observer",
+    "Shulker Box",
+    "White Shulker Box",
+    "Orange Shulker Box",
+    "Magenta Shulker Box",
+    "Light Blue Shulker Box",
+    "Yellow Shulker Box",
+    "Lime Shulker Box",
+    "Pink Shulker Box",
+    "Gray Shulker Box",
+    "Light Gray Shulker Box",
+    "Cyan Shulker Box",
+    "Purple Shulker Box",
+    "Blue Shulker Box",
+    "Brown Shulker Box",
+    "Green Shulker Box",
+    "Red Shulker Box",
+    "Black Shulker Box",
+    "White Glazed Terracotta",
+    "Orange Glazed Terracotta",
+    "Magenta Glazed Terracotta",
+    "Light Blue Glazed Terracotta",
+    "Yellow Glazed Terracotta",
+    "Lime Glazed Terracotta",
+    "Pink Glazed Terracotta",
+    "Gray Glazed Terracotta",
+    "Light Gray Glazed Terracotta",
+    "Cyan Glazed Terracotta",
+    "Purple Glazed Terracotta",
+    "Blue Glazed Terracotta",
+    "Brown Glazed Terracotta",
+    "Green Glazed Terracotta",
+    "Red Glazed Terracotta",
+    "Black Glazed Terracotta",
+    "White Concrete",
+    "Orange Concrete",
+    "Magenta Concrete",
+    "Light Blue Concrete",
+    "Yellow Concrete",
+    "Lime Concrete",
+    "Pink Concrete",
+    "Gray Concrete",
+    "Light Gray Concrete",
+    "Cyan Concrete",
+    "Purple Concrete",
+    "Blue Concrete",
+    "Brown Concrete",
+    "Green Concrete",
+    "Red Concrete",
+    "Black Concrete",
+    "White Concrete Powder",
+    "Orange Concrete Powder",
+    "Magenta Concrete Powder",
+    "Light Blue Concrete Powder",
+    "Yellow Concrete Powder",
+    "Lime Concrete Powder",
+    "Pink Concrete Powder",
+    "Gray Concrete Powder",
+    "Light Gray Concrete Powder",
+    "Cyan Concrete Powder",
+    "Purple Concrete Powder",
+    "Blue Concrete Powder",
+    "Brown Concrete Powder",
+    "Green Concrete Powder",
+    "Red Concrete Powder",
+    "Black Concrete Powder",
+    "Turtle Egg",
+    "Dead Tube Coral Block",
+    "Dead Brain Coral Block",
+    "Dead Bubble Coral Block",
+    "Dead Fire Coral Block",
+    "Dead Horn Coral Block",
+    "Tube Coral Block",
+    "Brain Coral Block",
+    "Bubble Coral Block",
+    "Fire Coral Block",
+    "Horn Coral Block",
+    "Tube Coral",
+    "Brain Coral",
+    "Bubble Coral",
+    "Fire Coral",
+    "Horn Coral",
+    "Dead Brain Coral",
+    "Dead Bubble Coral",
+    "Dead Fire Coral",
+    "Dead Horn Coral",
+    "Dead Tube Coral",
+    "Tube Coral Fan",
+    "Brain Coral Fan",
+    "Bubble Coral Fan",
+    "Fire Coral Fan",
+    "Horn Coral Fan",
+    "Dead Tube Coral Fan",
+    "Dead Brain Coral Fan",
+    "Dead Bubble Coral Fan",
+    "Dead Fire Coral Fan",
+    "Dead Horn Coral Fan",
+    "Blue Ice",
+    "Conduit",
+    "Polished Granite Stairs",
+    "Smooth Red Sandstone Stairs",
+    "Mossy Stone Brick Stairs",
+    "Polished Diorite Stairs",
+    "Mossy Cobblestone Stairs",
+    "End Stone Brick Stairs",
+    "Stone Stairs",
+    "Smooth Sandstone Stairs",
+    "Smooth Quartz Stairs",
+    "Granite Stairs",
+    "Andesite Stairs",
+    "Red Nether Brick Stairs",
+    "Polished Andesite Stairs",
+    "Diorite Stairs",
+    "Polished Granite Slab",
+    "Smooth Red Sandstone Slab",
+    "Mossy Stone Brick Slab",
+    "Polished Diorite Slab",
+    "Mossy Cobblestone Slab",
+    "End Stone Brick Slab",
+    "Smooth Sandstone Slab",
+    "Smooth Quartz Slab",
+    "Granite Slab",
+    "Andesite Slab",
+    "Red Nether Brick Slab",
+    "Polished Andesite Slab",
+    "Diorite Slab",
+    "Scaffolding",
+    "Iron Door",
+    "Oak Door",
+    "Spruce Door",
+    "Birch Door",
+    "Jungle Door",
+    "Acacia Door",
+    "Dark Oak Door",
+    "Crimson Door",
+    "Warped Door",
+    "Repeater",
+    "Comparator",
+    "Structure Block",
+    "Jigsaw",
+    "Turtle Shell",
+    "Scute",
+    "Flint and Steel",
+    "Apple",
+    "Bow",
+    "Arrow",
+    "Coal",
+    "Charcoal",
+    "Diamond",
+    "Iron Ingot",
+    "Gold Ingot",
+    "Netherite Ingot",
+    "Netherite Scrap",
+    "Wooden Sword",
+    "Wooden Shovel",
+    "Wooden Pickaxe",
+    "Wooden Axe",
+    "Wooden Hoe",
+    "Stone Sword",
+    "Stone Shovel",
+    "Stone Pickaxe",
+    "Stone Axe",
+    "Stone Hoe",
+    "Golden Sword",
+    "Golden Shovel",
+    "Golden Pickaxe",
+    "Golden Axe",
+    "Golden Hoe",
+    "Iron Sword",
+    "Iron Shovel",
+    "Iron Pickaxe",
+    "Iron Axe",
+    "Iron Hoe",
+    "Diamond Sword",
+    "Diamond Shovel",
+    "Diamond Pickaxe",
+    "Diamond Axe",
+    "Diamond Hoe",
+    "Netherite Sword",
+    "Netherite Shovel",
+    "Netherite Pickaxe",
+    "Netherite Axe",
+    "Netherite Hoe",
+    "Stick",
+    "Bowl",
+    "Mushroom Stew",
+    "String",
+    "Feather",
+    "Gunpowder",
+    "Wheat Seeds",
+    "Wheat",
+    "Bread",
+    "Leather Helmet",
+    "Leather Chestplate",
+    "Leather Leggings",
+    "Leather Boots",
+    "Chainmail Helmet",
+    "Chainmail Chestplate",
+    "Chainmail Leggings",
+    "Chainmail Boots",
+    "Iron Helmet",
+    "Iron Chestplate",
+    "Iron Leggings",
+    "Iron Boots",
+    "Diamond Helmet",
+    "Diamond Chestplate",
+    "Diamond Leggings",
+    "Diamond Boots",
+    "Golden Helmet",
+    "Golden Chestplate",
+    "Golden Leggings",
+    "Golden Boots",
+    "Netherite Helmet",
+    "Netherite Chestplate",
+    "Netherite Leggings",
+    "Netherite Boots",
+    "Flint",
+    "Raw Porkchop",
+    "Cooked Porkchop",
+    "Painting",
+    "Golden Apple",
+    "Enchanted Golden Apple",
+    "Oak Sign",
+    "Spruce Sign",
+    "Birch Sign",
+    "Jungle Sign",
+    "Acacia Sign",
+    "Dark Oak Sign",
+    "Crimson Sign",
+    "Warped Sign",
+    "Bucket",
+    "Water Bucket",
+    "Lava Bucket",
+    "Minecart",
+    "Saddle",
+    "Redstone Dust",
+    "Snowball",
+    "Oak Boat",
+    "Leather",
+    "Milk Bucket",
+    "Bucket of Pufferfish",
+    "Bucket of Salmon",
+    "Bucket of Cod",
+    "Bucket of Tropical Fish",
+    "Brick",
+    "Clay Ball",
+    "Dried Kelp Block",
+    "Paper",
+    "Book",
+    "Slimeball",
+    "Minecart with Chest",
+    "Minecart with Furnace",
+    "Egg",
+    "Compass",
+    "Fishing Rod",
+    "Clock",
+    "Glowstone Dust",
+    "Raw Cod",
+    "Raw Salmon",
+    "Tropical Fish",
+    "Pufferfish",
+    "Cooked Cod",
+    "Cooked Salmon",
+    "Ink Sac",
+    "Cocoa Beans",
+    "Lapis Lazuli",
+    "White Dye",
+    "Orange Dye",
+    "Magenta Dye",
+    "Light Blue Dye",
+    "Yellow Dye",
+    "Lime Dye",
+    "Pink Dye",
+    "Gray Dye",
+    "Light Gray Dye",
+    "Cyan Dye",
+    "Purple Dye",
+    "Blue Dye",
+    "Brown Dye",
+    "Green Dye",
+    "Red Dye",
+    "Black Dye",
+    "Bone Meal",
+    "Bone",
+    "Sugar",
+    "Cake",
+    "White Bed",
+    "Orange Bed",
+    "Magenta Bed",
+    "Light Blue Bed",
+    "Yellow Bed",
+    "Lime Bed",
+    "Pink Bed",
+    "Gray Bed",
+    "Light Gray Bed",
+    "Cyan Bed",
+    "Purple Bed",
+    "Blue Bed",
+    "Brown Bed",
+    "Green Bed",
+    "Red Bed",
+    "Black Bed",
+    "Cookie",
+    "Map",
+    "Shears",
+    "Melon Slice",
+    "Dried Kelp",
+    "Pumpkin Seeds",
+    "Melon Seeds",
+    "Raw Beef",
+    "Steak",
+    "Raw Chicken",
+    "Cooked Chicken",
+    "Rotten Flesh",
+    "Ender Pearl",
+    "Blaze Rod",
+    "Ghast Tear",
+    "Gold Nugget",
+    "Nether Wart",
+    "Potion",
+    "Glass Bottle",
+    "Spider Eye",
+    "Fermented Spider Eye",
+    "Blaze Powder",
+    "Magma Cream",
+    "Brewing Stand",
+    "Cauldron",
+    "Eye of Ender",
+    "Glistering Melon Slice",
+    "Bat Spawn Egg",
+    "Bee Spawn Egg",
+    "Blaze Spawn Egg",
+    "Cat Spawn Egg",
+    "Cave Spider Spawn Egg",
+    "Chicken Spawn Egg",
+    "Cod Spawn Egg",
+    "Cow Spawn Egg",
+    "Creeper Spawn Egg",
+    "Dolphin Spawn Egg",
+    "Donkey Spawn Egg",
+    "Drowned Spawn Egg",
+    "Elder Guardian Spawn Egg",
+    "Enderman Spawn Egg",
+    "Endermite Spawn Egg",
+    "Evoker Spawn Egg",
+    "Fox Spawn Egg",
+    "Ghast Spawn Egg",
+    "Guardian Spawn Egg",
+    "Hoglin Spawn Egg",
+    "Horse Spawn Egg",
+    "Husk Spawn Egg",
+    "Llama Spawn Egg",
+    "Magma Cube Spawn Egg",
+    "Mooshroom Spawn Egg",
+    "Mule Spawn Egg",
+    "Ocelot Spawn Egg",
+    "Panda Spawn Egg",
+    "Parrot Spawn Egg",
+    "Phantom Spawn Egg",
+    "Pig Spawn Egg",
+    "Piglin Spawn Egg",
+    "Piglin Brute Spawn Egg",
+    "Pillager Spawn Egg",
+    "Polar Bear Spawn Egg",
+    "Pufferfish Spawn Egg",
+    "Rabbit Spawn Egg",
+    "Ravager Spawn Egg",
+    "Salmon Spawn Egg",
+    "Sheep Spawn Egg",
+    "Shulker Spawn Egg",
+    "Silverfish Spawn Egg",
+    "Skeleton Spawn Egg",
+    "Skeleton Horse Spawn Egg",
+    "Slime Spawn Egg",
+    "Spider Spawn Egg",
+    "Squid Spawn Egg",
+    "Stray Spawn Egg",
+    "Strider Spawn Egg",
+    "Trader Llama Spawn Egg",
+    "Tropical Fish Spawn Egg",
+    "Turtle Spawn Egg",
+    "Vex Spawn Egg",
+    "Villager Spawn Egg",
+    "Vindicator Spawn Egg",
+    "Wandering Trader Spawn Egg",
+    "Witch Spawn Egg",
+    "Wither Skeleton Spawn Egg",
+    "Wolf Spawn Egg",
+    "Zoglin Spawn Egg",
+    "Zombie Spawn Egg",
+    "Zombie Horse Spawn Egg",
+    "Zombie Villager Spawn Egg",
+    "Zombified Piglin Spawn Egg",
+    "Bottle o' Enchanting",
+    "Fire Charge",
+    "Book and Quill",
+    "Written Book",
+    "Emerald",
+    "Item Frame",
+    "Flower Pot",
+    "Carrot",
+    "Potato",
+    "Baked Potato",
+    "Poisonous Potato",
+    "Empty Map",
+    "Golden Carrot",
+    "Skeleton Skull",
+    "Wither Skeleton Skull",
+    "Player Head",
+    "Zombie Head",
+    "Creeper Head",
+    "Dragon Head",
+    "Carrot on a Stick",
+    "Warped Fungus on a Stick",
+    "Nether Star",
+    "Pumpkin Pie",
+    "Firework Rocket",
+    "Firework Star",
+    "Enchanted Book",
+    "Nether Brick",
+    "Nether Quartz",
+    "Minecart with TNT",
+    "Minecart with Hopper",
+    "Prismarine Shard",
+    "Prismarine Crystals",
+    "Raw Rabbit",
+    "Cooked Rabbit",
+    "Rabbit Stew",
+    "Rabbit's Foot",
+    "Rabbit Hide",
+    "Armor Stand",
+    "Iron Horse Armor",
+    "Golden Horse Armor",
+    "Diamond Horse Armor",
+    "Leather Horse Armor",
+    "Lead",
+    "Name Tag",
+    "Minecart with Command Block",
+    "Raw Mutton",
+    "Cooked Mutton",
+    "White Banner",
+    "Orange Banner",
+    "Magenta Banner",
+    "Light Blue Banner",
+    "Yellow Banner",
+    "Lime Banner",
+    "Pink Banner",
+    "Gray Banner",
+    "Light Gray Banner",
+    "Cyan Banner",
+    "Purple Banner",
+    "Blue Banner",
+    "Brown Banner",
+    "Green Banner",
+    "Red Banner",
+    "Black Banner",
+    "End Crystal",
+    "Chorus Fruit",
+    "Popped Chorus Fruit",
+    "Beetroot",
+    "Beetroot Seeds",
+    "Beetroot Soup",
+    "Dragon's Breath",
+    "Splash Potion",
+    "Spectral Arrow",
+    "Tipped Arrow",
+    "Lingering Potion",
+    "Shield",
+    "Elytra",
+    "Spruce Boat",
+    "Birch Boat",
+    "Jungle Boat",
+    "Acacia Boat",
+    "Dark Oak Boat",
+    "Totem of Undying",
+    "Shulker Shell",
+    "Iron Nugget",
+    "Knowledge Book",
+    "Debug Stick",
+    "Music Disc (13)",
+    "Music Disc (Cat)",
+    "Music Disc (Blocks)",
+    "Music Disc (Chirp)",
+    "Music Disc (Far)",
+    "Music Disc (Mall)",
+    "Music Disc (Mellohi)",
+    "Music Disc (Stal)",
+    "Music Disc (Strad)",
+    "Music Disc (Ward)",
+    "Music Disc (11)",
+    "Music Disc (Wait)",
+    "Music Disc (Pigstep)",
+    "Trident",
+    "Phantom Membrane",
+    "Nautilus Shell",
+    "Heart of the Sea",
+    "Crossbow",
+    "Suspicious Stew",
+    "Loom",
+    "Flower Banner Pattern",
+    "Creeper Banner Pattern",
+    "Skull Banner Pattern",
+    "Mojang Banner Pattern",
+    "Globe Banner Pattern",
+    "Piglin Banner Pattern",
+    "Composter",
+    "Barrel",
+    "Smoker",
+    "Blast Furnace",
+    "Cartography Table",
+    "Fletching Table",
+    "Grindstone",
+    "Lectern",
+    "Smithing Table",
+    "Stonecutter",
+    "Bell",
+    "Lantern",
+    "Soul Lantern",
+    "Sweet Berries",
+    "Campfire",
+    "Soul Campfire",
+    "Shroomlight",
+    "Honeycomb",
+    "Bee Nest",
+    "Beehive",
+    "Honey Bottle",
+    "Honey Block",
+    "Honeycomb Block",
+    "Lodestone",
+    "Block of Netherite",
+    "Ancient Debris",
+    "Target",
+    "Crying Obsidian",
+    "Blackstone",
+    "Blackstone Slab",
+    "Blackstone Stairs",
+    "Gilded Blackstone",
+    "Polished Blackstone",
+    "Polished Blackstone Slab",
+    "Polished Blackstone Stairs",
+    "Chiseled Polished Blackstone",
+    "Polished Blackstone Bricks",
+    "Polished Blackstone Brick Slab",
+    "Polished Blackstone Brick Stairs",
+    "Cracked Polished Blackstone Bricks",
+    "Respawn Anchor",
+];
+
+const PLAYER: i64 = 106;
+
+const CUSTOM: &[(i64, &str)] = &[
+    (0, "Games Quit"),
+    (1, "Time Played (ticks)"),
+    (2, "Time Since Last Death (ticks)"),
+    (3, "Time Since Last Rest (ticks)"),
+    (4, "Sneak Time (ticks)"),
+    (5, "Distance Walked (cm)"),
+    (6, "Distance Crouched (cm)"),
+    (7, "Distance Sprinted (cm)"),
+    (8, "Distance Walked on Water (cm)"),
+    (9, "Distance Fallen (cm)"),
+    (10, "Distance Climbed (cm)"),
+    (11, "Distance Flown (cm)"),
+    (12, "Distance Walked under Water (cm)"),
+    (18, "Distance Swum (cm)"),
+    (20, "Jumps"),
+    (21, "Items Dropped"),
+    (22, "Damage Dealt"),
+    (23, "Damage Dealt (Absorbed)"),
+    (24, "Damage Dealt (Resisted)"),
+    (25, "Damage Taken"),
+    (26, "Damage Blocked by Shield"),
+    (27, "Damage Absorbed"),
+    (28, "Damage Resisted"),
+    (29, "Deaths"),
+    (30, "Mob Kills"),
+    (31, "Animals Bred"),
+    (32, "Player Kills"),
+    (33, "Fish Caught"),
+];
+
+// the name of the thing counted by the statistic
+pub fn statistic_name(category: i64, id: i64) -> String {
+    let known = match category {
+        0 => by_id(&BLOCKS, id),
+        1..=5 => return item_name(id),
+        6 | 7 if id == PLAYER => Some("Player"),
+        6 | 7 => MOBS.get_by_left(&id).copied(),
+        8 => find(CUSTOM, id),
+        _ => None,
+    };
+
+    match known {
+        Some(name) => name.to_string(),
+        None => format!("#{}", id),
+    }
+}
+
+pub fn item_name(id: i64) -> String {
+    match by_id(&ITEMS, id) {
+        Some(name) => name.to_string(),
+        None => format!("Item #{}", id),
+    }
+}
+
+pub fn category_name(category: i64) -> String {
+    match CATEGORIES.get(category as usize) {
+        Some(name) => name.to_string(),
+        None => format!("Category #{}", category),
+    }
+}
+
+fn find(table: &[(i64, &'static str)], id: i64) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|&&(known_id, _)| known_id == id)
+        .map(|&(_, name)| name)
+}
+
+fn by_id(table: &[&'static str], id: i64) -> Option<&'static str> {
+    if id < 0 {
+        return None;
+    }
+    table.get(id as usize).copied()
+}
@@ -0,0 +1,99 @@
+// commands that trusted players can send to the bot with /msg
+
+use crate::mobs::MOBS;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Status,
+    Sneak(bool),
+    Attack(i64, bool), // mob id, whether to attack it
+    Eat,
+    Quit,
+}
+
+// the command in a private message, None if it's not meant for the bot: not from an owner or not starting with !
+pub fn command(owners: &[String], sender: &str, message: &str) -> Option<Result<Command, String>> {
+    let is_owner = owners
+        .iter()
+        .any(|owner| owner.eq_ignore_ascii_case(sender));
+    if !is_owner || !message.starts_with('!') {
+        return None;
+    }
+    Some(parse(message))
+}
+
+// parses a private message, returns an error message that can be sent back if it's not a valid command
+pub fn parse(message: &str) -> Result<Command, String> {
+    let words: Vec<&str> = message.split_whitespace().collect();
+
+    match words.as_slice() {
+        ["!status"] => Ok(Command::Status),
+        ["!sneak", on_off] => Ok(Command::Sneak(parse_on_off(on_off)?)),
+        ["!attack", mob @ .., on_off] if !mob.is_empty() => {
+            let on = parse_on_off(on_off)?;
+            let name = mob.join(" ").replace('_', " ");
+            match MOBS
+                .iter()
+                .find(|(_, mob_name)| mob_name.eq_ignore_ascii_case(&name))
+            {
+                Some((id, _)) => Ok(Command::Attack(*id, on)),
+                None => Err(format!("Unknown mob {:?}", name)),
+            }
+        }
+        ["!eat"] => Ok(Command::Eat),
+        ["!quit"] => Ok(Command::Quit),
+        _ => Err("Commands: !status, !sneak on/off, !attack <mob> on/off, !eat, !quit".to_string()),
+    }
+}
+
+fn parse_on_off(word: &str) -> Result<bool, String> {
+    match word {
+        "on" => Ok(true),
+        "off" => Ok(false),
+        _ => Err(format!("Expected on or off, got {:?}", word)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands() {
+        assert_eq!(parse("!status"), Ok(Command::Status));
+        assert_eq!(parse(" !sneak  on "), Ok(Command::Sneak(true)));
+        assert_eq!(parse("!sneak off"), Ok(Command::Sneak(false)));
+        assert_eq!(parse("!attack zombie on"), Ok(Command::Attack(102, true)));
+        assert_eq!(
+            parse("!attack Cave Spider off"),
+            Ok(Command::Attack(8, false))
+        );
+        assert_eq!(
+            parse("!attack wither_skeleton on"),
+            Ok(Command::Attack(98, true))
+        );
+        assert_eq!(parse("!eat"), Ok(Command::Eat));
+        assert_eq!(parse("!quit"), Ok(Command::Quit));
+    }
+
+    #[test]
+    fn invalid_commands() {
+        assert!(parse("!sneak").is_err());
+        assert!(parse("!sneak yes").is_err());
+        assert!(parse("!attack on").is_err());
+        assert!(parse("!attack herobrine on").is_err());
+        assert!(parse("!quit now").is_err());
+        assert!(parse("!fly").is_err());
+    }
+
+    #[test]
+    fn only_owners() {
+        let owners = vec!["Owner".to_string()];
+        assert_eq!(command(&owners, "owner", "!quit"), Some(Ok(Command::Quit)));
+        assert_eq!(command(&owners, "Mallory", "!quit"), None);
+        assert_eq!(command(&owners, "Owner2", "!quit"), None);
+        assert_eq!(command(&[], "Owner", "!quit"), None);
+        // just talking to the bot
+        assert_eq!(command(&owners, "Owner", "hi"), None);
+    }
+}
@@ -0,0 +1,171 @@
+// chat messages or commands that are sent automatically
+//
+// every line of the schedule looks like one of these:
+//   every 10m /afk
+//   at 18:30 /warp shop
+//   join /home farm
+// lines starting with # are comments
+
+use chrono::NaiveTime;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Schedule {
+    Every(Duration),
+    At(NaiveTime), // local time, once a day
+    OnJoin,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScheduledMessage {
+    pub schedule: Schedule,
+    pub message: String,
+}
+
+// parses every line that isn't empty or a comment, returning the messages and the errors of the invalid lines
+pub fn parse(text: &str) -> (Vec<ScheduledMessage>, Vec<String>) {
+    let mut messages = Vec::new();
+    let mut errors = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match parse_line(line) {
+            Ok(message) => messages.push(message),
+            Err(e) => errors.push(e),
+        }
+    }
+
+    (messages, errors)
+}
+
+pub fn parse_line(line: &str) -> Result<ScheduledMessage, String> {
+    let line = line.trim();
+    let (kind, rest) = split_word(line);
+
+    let (schedule, message) = match kind {
+        "every" => {
+            let (interval, message) = split_word(rest);
+            (Schedule::Every(parse_duration(interval)?), message)
+        }
+        "at" => {
+            let (time, message) = split_word(rest);
+            let time = NaiveTime::parse_from_str(time, "%H:%M")
+                .map_err(|_| format!("Invalid time {:?}, expected HH:MM", time))?;
+            (Schedule::At(time), message)
+        }
+        "join" => (Schedule::OnJoin, rest),
+        _ => {
+            return Err(format!(
+                "Invalid schedule {:?}, expected every, at or join",
+                kind
+            ))
+        }
+    };
+
+    if message.is_empty() {
+        return Err(format!("No message to send in {:?}", line));
+    }
+    if message.len() > 256 {
+        return Err(format!("Message too long in {:?}", line));
+    }
+
+    Ok(ScheduledMessage {
+        schedule,
+        message: message.to_string(),
+    })
+}
+
+// parses durations like "90s", "10m", "2h" or just "30" for seconds
+fn parse_duration(duration: &str) -> Result<Duration, String> {
+    let (number, multiplier) = match duration.chars().last() {
+        Some('s') => (&duration[..duration.len() - 1], 1),
+        Some('m') => (&duration[..duration.len() - 1], 60),
+        Some('h') => (&duration[..duration.len() - 1], 60 * 60),
+        _ => (duration, 1),
+    };
+
+    match number.parse::<u64>() {
+        Ok(number) if number > 0 => Ok(Duration::from_secs(number * multiplier)),
+        _ => Err(format!("Invalid interval {:?}", duration)),
+    }
+}
+
+fn split_word(s: &str) -> (&str, &str) {
+    match s.find(char::is_whitespace) {
+        Some(i) => (&s[..i], s[i..].trim_start()),
+        None => (s, ""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intervals() {
+        let every = |line| parse_line(line).map(|message| message.schedule);
+        assert_eq!(
+            every("every 30 /afk"),
+            Ok(Schedule::Every(Duration::from_secs(30)))
+        );
+        assert_eq!(
+            every("every 90s /afk"),
+            Ok(Schedule::Every(Duration::from_secs(90)))
+        );
+        assert_eq!(
+            every("every 10m /afk"),
+            Ok(Schedule::Every(Duration::from_secs(600)))
+        );
+        assert_eq!(
+            every("every 2h /afk"),
+            Ok(Schedule::Every(Duration::from_secs(7200)))
+        );
+    }
+
+    #[test]
+    fn messages() {
+        assert_eq!(
+            parse_line("  at 18:30   /warp shop  "),
+            Ok(ScheduledMessage {
+                schedule: Schedule::At(NaiveTime::from_hms(18, 30, 0)),
+                message: "/warp shop".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_line("join /home farm"),
+            Ok(ScheduledMessage {
+                schedule: Schedule::OnJoin,
+                message: "/home farm".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_lines() {
+        assert!(parse_line("every 10d /afk").is_err());
+        assert!(parse_line("every m /afk").is_err());
+        assert!(parse_line("every 0m /afk").is_err());
+        assert!(parse_line("every -5 /afk").is_err());
+        assert!(parse_line("every 10m").is_err());
+        assert!(parse_line("at 25:00 /afk").is_err());
+        assert!(parse_line("sometimes /afk").is_err());
+        assert!(parse_line(&format!("join {}", "a".repeat(257))).is_err());
+    }
+
+    #[test]
+    fn blank_and_comment_lines() {
+        let (messages, errors) = parse(
+            "# keep the bot from being kicked\n\nevery 10m /afk\n   \n  # join /spawn\nnonsense\n",
+        );
+        assert_eq!(
+            messages,
+            vec![ScheduledMessage {
+                schedule: Schedule::Every(Duration::from_secs(600)),
+                message: "/afk".to_string(),
+            }]
+        );
+        assert_eq!(errors.len(), 1);
+    }
+}
@@ -0,0 +1,427 @@
+// farm scripts written in Rhai, loaded from a folder and reloaded when they change
+//
+// a script can define any of these functions, all of them getting the bot's state as a map:
+//   on_join(bot), on_tick(bot), on_chat(bot, message)
+// and can call chat(text), attack(mob_id), use_item(), look(yaw, pitch), select_slot(1-9) and print(text)
+// only the functions are used, and `this` in them is a map that's kept between the calls
+
+use crate::behavior::{Behavior, Context};
+use crate::bot::State;
+use crate::chat;
+use crate::clone_all;
+use crate::datatypes::*;
+use crate::mobs::MOBS;
+use crate::packets::{ClientBound, ServerBound};
+use crate::registries;
+use async_trait::async_trait;
+use rhai::{Array, CallFnOptions, Dynamic, Engine, Map, Scope, AST};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::io;
+
+// what a script asked for, done right after its function returns
+enum Action {
+    Chat(String),
+    Attack(i64), // entity id
+    UseItem,
+    Look(f32, f32), // yaw, pitch
+    SelectSlot(i64),
+    Print(String),
+}
+
+struct Script {
+    ast: AST,
+    modified: SystemTime,
+    memory: Dynamic, // `this` in the script, reset when reloaded
+}
+
+pub struct Scripts {
+    folder: PathBuf,
+    engine: Engine,
+    actions: Arc<Mutex<Vec<Action>>>,
+    scripts: BTreeMap<PathBuf, Script>,
+    broken: BTreeMap<PathBuf, SystemTime>, // not tried again until the file changes
+    folder_error: bool,                    // so that it's logged only once
+    last_scan: Option<Instant>,
+}
+
+impl Scripts {
+    pub fn new(folder: PathBuf) -> Self {
+        let actions = Arc::new(Mutex::new(Vec::new()));
+        Self {
+            folder,
+            engine: engine(&actions),
+            actions,
+            scripts: BTreeMap::new(),
+            broken: BTreeMap::new(),
+            folder_error: false,
+            last_scan: None,
+        }
+    }
+
+    // loads the new and changed scripts and forgets the removed ones, returns the ones that were loaded
+    // the files are read through tokio so that a slow disk doesn't hold up the other bots
+    async fn scan(&mut self, context: &Context) -> Vec<PathBuf> {
+        let mut entries = match tokio::fs::read_dir(&self.folder).await {
+            Ok(entries) => entries,
+            Err(e) => {
+                if !self.folder_error {
+                    context.warn(format!(
+                        "Couldn't read the scripts folder {}: {}",
+                        self.folder.display(),
+                        e
+                    ));
+                    self.folder_error = true;
+                }
+                return Vec::new();
+            }
+        };
+        self.folder_error = false;
+
+        let mut found = BTreeMap::new();
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "rhai")
+            {
+                if let Ok(modified) = entry
+                    .metadata()
+                    .await
+                    .and_then(|metadata| metadata.modified())
+                {
+                    found.insert(path, modified);
+                }
+            }
+        }
+
+        let removed: Vec<PathBuf> = self
+            .scripts
+            .keys()
+            .filter(|path| !found.contains_key(*path))
+            .cloned()
+            .collect();
+        for path in removed {
+            self.scripts.remove(&path);
+            context.log(format!("Unloaded script {}", script_name(&path)));
+        }
+        self.broken.retain(|path, _| found.contains_key(path));
+
+        let mut loaded = Vec::new();
+        for (path, modified) in found {
+            let known = match self.scripts.get(&path) {
+                Some(script) => Some(script.modified),
+                None => self.broken.get(&path).copied(),
+            };
+            if known == Some(modified) {
+                continue;
+            }
+            self.scripts.remove(&path);
+            self.broken.remove(&path);
+
+            let compiled = match tokio::fs::read_to_string(&path).await {
+                Ok(text) => self.engine.compile(&text).map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            match compiled {
+                Ok(ast) => {
+                    context.log(format!(
+                        "{} script {}",
+                        if known.is_some() {
+                            "Reloaded"
+                        } else {
+                            "Loaded"
+                        },
+                        script_name(&path)
+                    ));
+                    let script = Script {
+                        ast,
+                        modified,
+                        memory: Map::new().into(),
+                    };
+                    self.scripts.insert(path.clone(), script);
+                    loaded.push(path);
+                }
+                Err(e) => {
+                    context.warn(format!(
+                        "Couldn't load script {}: {}",
+                        script_name(&path),
+                        e
+                    ));
+                    self.broken.insert(path, modified);
+                }
+            }
+        }
+        loaded
+    }
+
+    // calls the function in the scripts that have it, doing what each one asks for right after
+    async fn call_all(
+        &mut self,
+        context: &Context,
+        paths: &[PathBuf],
+        function: &str,
+        args: Vec<Dynamic>,
+    ) -> io::Result<()> {
+        if paths.is_empty() {
+            return Ok(());
+        }
+        let bot: Dynamic = snapshot(&*context.state().await).into();
+        for path in paths {
+            let mut all_args = vec![bot.clone()];
+            all_args.extend(args.iter().cloned());
+            self.call(context, path, function, all_args);
+            self.perform(context, &script_name(path)).await?;
+        }
+        Ok(())
+    }
+
+    fn call(&mut self, context: &Context, path: &Path, function: &str, args: Vec<Dynamic>) {
+        let script = match self.scripts.get_mut(path) {
+            Some(script) => script,
+            None => return,
+        };
+        let defined = script
+            .ast
+            .iter_functions()
+            .any(|f| f.name == function && f.params.len() == args.len());
+        if !defined {
+            return;
+        }
+
+        let options = CallFnOptions::new()
+            .eval_ast(false)
+            .bind_this_ptr(&mut script.memory);
+        let res = self.engine.call_fn_with_options::<Dynamic>(
+            options,
+            &mut Scope::new(),
+            &script.ast,
+            function,
+            args,
+        );
+        if let Err(e) = res {
+            // it would most likely fail the same way every tick
+            context.warn(format!(
+                "Script {} failed in {}, stopped it until it's changed: {}",
+                script_name(path),
+                function,
+                e
+            ));
+            let script = self.scripts.remove(path).unwrap();
+            self.broken.insert(path.to_path_buf(), script.modified);
+        }
+    }
+
+    async fn perform(&mut self, context: &Context, script_name: &str) -> io::Result<()> {
+        let actions = std::mem::take(&mut *self.actions.lock().unwrap());
+        for action in actions {
+            match action {
+                Action::Print(text) => context.log(format!("[{}] {}", script_name, text)),
+                Action::Chat(message) => {
+                    context
+                        .send(ServerBound::ChatMessage(MString(message)))
+                        .await?;
+                }
+                Action::Attack(entity_id) => {
+                    let sneaking = context.settings().await.sneak;
+                    context
+                        .send(ServerBound::InteractEntity(
+                            VarInt(entity_id),
+                            VarInt(1),
+                            sneaking,
+                        ))
+                        .await?;
+                    context.send(ServerBound::Animation(VarInt(0))).await?;
+                }
+                Action::UseItem => context.send(ServerBound::UseItem(VarInt(0))).await?,
+                Action::Look(yaw, pitch) => {
+                    let on_ground = {
+                        let mut state_lock = context.state().await;
+                        state_lock.my_rotation = (yaw, pitch);
+                        state_lock.on_ground
+                    };
+                    context
+                        .send(ServerBound::PlayerRotation(yaw, pitch, on_ground))
+                        .await?;
+                }
+                Action::SelectSlot(slot) => {
+                    if !(1..=9).contains(&slot) {
+                        context.warn(format!("[{}] There's no hotbar slot {}", script_name, slot));
+                        continue;
+                    }
+                    let slot = (slot - 1) as u8;
+                    // it's the weapon slot from now on, so that it's not switched back
+                    context.settings().await.weapon = slot;
+                    context.state().await.held_item = slot;
+                    context
+                        .send(ServerBound::HeldItemChange(slot as i16))
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Behavior for Scripts {
+    fn name(&self) -> &str {
+        "scripts"
+    }
+
+    async fn on_join(&mut self, context: &mut Context) -> io::Result<()> {
+        self.last_scan = Some(Instant::now());
+        self.scan(context).await;
+        let paths: Vec<PathBuf> = self.scripts.keys().cloned().collect();
+        self.call_all(context, &paths, "on_join", Vec::new()).await
+    }
+
+    async fn on_tick(&mut self, context: &mut Context) -> io::Result<()> {
+        // checking the files every second is plenty
+        let scan = match self.last_scan {
+            Some(last_scan) => last_scan.elapsed() >= Duration::from_secs(1),
+            None => true,
+        };
+        if scan {
+            self.last_scan = Some(Instant::now());
+            // the bot has already joined, so the new scripts are told about it right away
+            let loaded = self.scan(context).await;
+            self.call_all(context, &loaded, "on_join", Vec::new())
+                .await?;
+        }
+
+        let paths: Vec<PathBuf> = self.scripts.keys().cloned().collect();
+        self.call_all(context, &paths, "on_tick", Vec::new()).await
+    }
+
+    async fn on_packet(&mut self, context: &mut Context, packet: &ClientBound) -> io::Result<()> {
+        if let ClientBound::ChatMessage(json, position, _) = packet {
+            // game info messages are shown above the hotbar and are not really chat
+            if *position != 2 && !self.scripts.is_empty() {
+                let message = chat::to_plain_text(&json.0);
+                let paths: Vec<PathBuf> = self.scripts.keys().cloned().collect();
+                self.call_all(context, &paths, "on_chat", vec![message.into()])
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn engine(actions: &Arc<Mutex<Vec<Action>>>) -> Engine {
+    let mut engine = Engine::new();
+    // a script stuck in a loop would hold up the bot
+    engine.set_max_operations(100_000);
+
+    engine.on_print({
+        clone_all![actions];
+        move |text| {
+            actions
+                .lock()
+                .unwrap()
+                .push(Action::Print(text.to_string()))
+        }
+    });
+    engine.register_fn("chat", {
+        clone_all![actions];
+        move |message: &str| {
+            actions
+                .lock()
+                .unwrap()
+                .push(Action::Chat(message.to_string()))
+        }
+    });
+    engine.register_fn("attack", {
+        clone_all![actions];
+        move |entity_id: i64| actions.lock().unwrap().push(Action::Attack(entity_id))
+    });
+    engine.register_fn("use_item", {
+        clone_all![actions];
+        move || actions.lock().unwrap().push(Action::UseItem)
+    });
+    engine.register_fn("look", {
+        clone_all![actions];
+        move |yaw: f64, pitch: f64| {
+            actions
+                .lock()
+                .unwrap()
+                .push(Action::Look(yaw as f32, pitch as f32))
+        }
+    });
+    engine.register_fn("select_slot", {
+        clone_all![actions];
+        move |slot: i64| actions.lock().unwrap().push(Action::SelectSlot(slot))
+    });
+
+    engine
+}
+
+// what the scripts get to know about the bot
+fn snapshot(state: &State) -> Map {
+    let my_pos = state.my_pos;
+    let mut mobs: Vec<(f64, Map)> = state
+        .mobs
+        .values()
+        .map(|mob| {
+            let distance = ((my_pos.0 - mob.pos.0).powi(2)
+                + (my_pos.1 - mob.pos.1).powi(2)
+                + (my_pos.2 - mob.pos.2).powi(2))
+            .sqrt();
+            let name = match MOBS.get_by_left(&mob.entity_type.0) {
+                Some(name) => name.to_string(),
+                None => format!("#{}", mob.entity_type.0),
+            };
+            let mut map = Map::new();
+            map.insert("id".into(), mob.entity_id.0.into());
+            map.insert("type".into(), name.into());
+            map.insert("x".into(), mob.pos.0.into());
+            map.insert("y".into(), mob.pos.1.into());
+            map.insert("z".into(), mob.pos.2.into());
+            map.insert("distance".into(), distance.into());
+            (distance, map)
+        })
+        .collect();
+    // the nearest first
+    mobs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    let mobs: Array = mobs.into_iter().map(|(_, map)| map.into()).collect();
+
+    // empty slots are ()
+    let hotbar: Array = state
+        .hotbar
+        .iter()
+        .map(|slot| match slot {
+            Slot::Present(id, count) => {
+                let mut map = Map::new();
+                map.insert("id".into(), id.0.into());
+                map.insert("name".into(), registries::item_name(id.0).into());
+                map.insert("count".into(), (*count as i64).into());
+                map.into()
+            }
+            Slot::NotPresent => Dynamic::UNIT,
+        })
+        .collect();
+
+    let mut bot = Map::new();
+    bot.insert("x".into(), my_pos.0.into());
+    bot.insert("y".into(), my_pos.1.into());
+    bot.insert("z".into(), my_pos.2.into());
+    bot.insert("yaw".into(), (state.my_rotation.0 as f64).into());
+    bot.insert("pitch".into(), (state.my_rotation.1 as f64).into());
+    bot.insert("on_ground".into(), state.on_ground.into());
+    bot.insert("health".into(), (state.health as f64).into());
+    bot.insert("food".into(), (state.food as f64).into());
+    bot.insert("sneaking".into(), state.sneaking.into());
+    bot.insert("slot".into(), (state.held_item as i64 + 1).into());
+    bot.insert("hotbar".into(), hotbar.into());
+    bot.insert("mobs".into(), mobs.into());
+    bot
+}
+
+fn script_name(path: &Path) -> String {
+    match path.file_name() {
+        Some(name) => name.to_string_lossy().to_string(),
+        None => path.display().to_string(),
+    }
+}
@@ -0,0 +1,304 @@
+// posts the important events to a chat app, so that someone finds out about them without watching the log
+// at most one message is posted every few seconds, the events that happen meanwhile are posted together
+
+use crate::handle::Event;
+use crate::Disconnect;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+// name, description
+pub const EVENTS: [(&str, &str); 5] = [
+    ("low_health", "Disconnected because the health was too low"),
+    ("kicked", "Kicked by the server"),
+    ("died", "Died"),
+    ("item_broken", "A weapon, tool or armor piece broke"),
+    ("player_nearby", "Another player came close"),
+];
+
+// the messages that are left out when too many events happen at once
+const MAX_LINES: usize = 10;
+// the longest wait a server can ask for, so that a bad Retry-After doesn't stop the posting for good
+const MAX_RETRY_AFTER: f64 = 300.0;
+// for connecting, reading and writing
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookSettings {
+    pub url: String,         // empty to not post anything
+    pub format: String,      // "discord", "slack", or "custom" to use the template
+    pub template: String,    // JSON with {message} where the text goes, for other apps
+    pub events: Vec<String>, // the ones to post, by name
+    pub min_interval: u64,   // seconds between two posts
+    pub retries: u32,        // how many more times to try when posting fails
+}
+
+impl Default for WebhookSettings {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            format: "discord".to_string(),
+            template: String::new(),
+            events: EVENTS.iter().map(|(name, _)| name.to_string()).collect(),
+            min_interval: 5,
+            retries: 3,
+        }
+    }
+}
+
+impl WebhookSettings {
+    fn template(&self) -> Result<&str, String> {
+        match self.format.as_str() {
+            "discord" => Ok(r#"{"content": "{message}"}"#),
+            "slack" => Ok(r#"{"text": "{message}"}"#),
+            "custom" if self.template.contains("{message}") => Ok(&self.template),
+            "custom" => Err("The webhook template has no {message} in it".to_string()),
+            format => Err(format!("Unknown webhook format {:?}", format)),
+        }
+    }
+}
+
+// passes the events on to the task that posts them
+#[derive(Debug, Clone)]
+pub struct Webhooks {
+    events: Arc<Vec<String>>,
+    sender: mpsc::UnboundedSender<String>,
+}
+
+impl Webhooks {
+    // the task must be spawned for anything to be posted, it ends once every handle has been dropped
+    // and the last messages have been posted
+    pub fn new<W: FnMut(String) + Send + 'static>(
+        settings: WebhookSettings,
+        mut write_to_log: W,
+    ) -> Result<(Webhooks, impl Future<Output = ()>), String> {
+        let url = parse_url(&settings.url)?;
+        let template = settings.template()?.to_string();
+        let (sender, mut receiver) = mpsc::unbounded_channel::<String>();
+        let webhooks = Webhooks {
+            events: Arc::new(settings.events.clone()),
+            sender,
+        };
+
+        let interval = Duration::from_secs(settings.min_interval);
+        let task = async move {
+            let mut pending = Vec::new();
+            let mut next_post = Instant::now();
+            let mut open = true;
+            while open || !pending.is_empty() {
+                tokio::select! {
+                    message = receiver.recv(), if open => match message {
+                        Some(message) => pending.push(message),
+                        None => open = false,
+                    },
+                    _ = tokio::time::sleep_until(next_post), if !pending.is_empty() => {
+                        let mut text = pending[..pending.len().min(MAX_LINES)].join("\n");
+                        if pending.len() > MAX_LINES {
+                            text.push_str(&format!("\n...and {} more", pending.len() - MAX_LINES));
+                        }
+                        pending.clear();
+                        let body = template.replace("{message}", &json_escape(&text));
+                        if let Err(e) = post_with_retries(&url, body, settings.retries).await {
+                            write_to_log(format!("Couldn't post to the webhook: {}", e));
+                        }
+                        next_post = Instant::now() + interval;
+                    }
+                }
+            }
+        };
+        Ok((webhooks, task))
+    }
+
+    pub fn notify(&self, username: &str, event: &Event) {
+        let (name, text) = match event {
+            Event::Disconnected(Disconnect::LowHealth) => (
+                "low_health",
+                "Disconnected because the health was too low.".to_string(),
+            ),
+            Event::Kicked(reason) => ("kicked", format!("Kicked: {}", reason)),
+            Event::Died { message, pos } => (
+                "died",
+                format!(
+                    "Died at {:.0} {:.0} {:.0}: {}",
+                    pos.0, pos.1, pos.2, message
+                ),
+            ),
+            Event::ItemBroken(item) => ("item_broken", format!("{} broke.", item)),
+            Event::PlayerNearby { name, distance } => (
+                "player_nearby",
+                format!("{} is {:.0} blocks away.", name, distance),
+            ),
+            _ => return,
+        };
+        if self.events.iter().any(|event| event == name) {
+            // fails only if the task has stopped
+            let _ = self.sender.send(format!("[{}] {}", username, text));
+        }
+    }
+}
+
+// the contents of a JSON string, without the quotes
+fn json_escape(text: &str) -> String {
+    let quoted = serde_json::to_string(text).unwrap();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+#[derive(Debug, Clone)]
+struct Url {
+    tls: bool,
+    host: String,
+    port: u16,
+    path: String,
+}
+
+fn parse_url(url: &str) -> Result<Url, String> {
+    let (tls, rest) = if let Some(rest) = url.strip_prefix("https://") {
+        (true, rest)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        (false, rest)
+    } else {
+        return Err("The webhook URL should start with https://".to_string());
+    };
+    let (authority, path) = match rest.find('/') {
+        Some(slash) => (&rest[..slash], &rest[slash..]),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.rfind(':') {
+        Some(colon) => match authority[colon + 1..].parse::<u16>() {
+            Ok(port) => (&authority[..colon], port),
+            Err(_) => return Err("Bad port in the webhook URL".to_string()),
+        },
+        None => (authority, if tls { 443 } else { 80 }),
+    };
+    if host.is_empty() {
+        return Err("The webhook URL has no host".to_string());
+    }
+    Ok(Url {
+        tls,
+        host: host.to_string(),
+        port,
+        path: path.to_string(),
+    })
+}
+
+// waits longer after every failed try, or as long as the server asks to when posting too often
+async fn post_with_retries(url: &Url, body: String, retries: u32) -> Result<(), String> {
+    let mut tries = 0;
+    loop {
+        let result = tokio::task::spawn_blocking({
+            let url = url.clone();
+            let body = body.clone();
+            move || post(&url, &body)
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let backoff = Duration::from_secs(1 << tries.min(6));
+        let (error, wait) = match result {
+            Ok((status, _)) if status < 300 => return Ok(()),
+            Ok((429, retry_after)) => (
+                "Posting too often".to_string(),
+                retry_after.unwrap_or(backoff),
+            ),
+            Ok((status, _)) if status >= 500 => {
+                (format!("The server answered {}", status), backoff)
+            }
+            // the same request would be refused again
+            Ok((status, _)) => return Err(format!("The server answered {}", status)),
+            Err(e) => (e.to_string(), backoff),
+        };
+        if tries >= retries {
+            return Err(error);
+        }
+        tries += 1;
+        tokio::time::sleep(wait).await;
+    }
+}
+
+// blocking, so it has to be run on its own thread
+// returns the status code, and how long to wait when posting too often
+fn post(url: &Url, body: &str) -> io::Result<(u16, Option<Duration>)> {
+    // the first address that works, like TcpStream::connect, but without waiting for the OS to give up
+    let mut last_error = None;
+    let mut stream = None;
+    for address in (url.host.as_str(), url.port).to_socket_addrs()? {
+        match TcpStream::connect_timeout(&address, TIMEOUT) {
+            Ok(connected) => {
+                stream = Some(connected);
+                break;
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+    let mut stream = match (stream, last_error) {
+        (Some(stream), _) => stream,
+        (None, Some(e)) => return Err(e),
+        (None, None) => {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "The webhook host has no addresses",
+            ))
+        }
+    };
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    let request = format!(
+        "POST {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: mc_afk_bot\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        url.path,
+        url.host,
+        body.len(),
+        body
+    );
+
+    if !url.tls {
+        return exchange(&mut stream, &request);
+    }
+    let mut config = rustls::ClientConfig::new();
+    config
+        .root_store
+        .add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
+    let host = webpki::DNSNameRef::try_from_ascii_str(&url.host)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Bad host name"))?;
+    let session = rustls::ClientSession::new(&Arc::new(config), host);
+    exchange(&mut rustls::StreamOwned::new(session, stream), &request)
+}
+
+// only the head of the response is read, the body doesn't matter
+fn exchange<S: Read + Write>(stream: &mut S, request: &str) -> io::Result<(u16, Option<Duration>)> {
+    stream.write_all(request.as_bytes())?;
+    stream.flush()?;
+
+    let mut response = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !response.windows(4).any(|window| window == b"\r\n\r\n") && response.len() < 16 * 1024 {
+        let read = stream.read(&mut chunk)?;
+        if read == 0 {
+            break;
+        }
+        response.extend_from_slice(&chunk[..read]);
+    }
+
+    let response = String::from_utf8_lossy(&response);
+    let mut lines = response.lines();
+    let status = lines
+        .next()
+        .and_then(|status_line| status_line.split(' ').nth(1))
+        .and_then(|status| status.parse::<u16>().ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Not an HTTP response"))?;
+    // in seconds, which Discord sends with a fraction
+    let retry_after = lines
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("retry-after"))
+        .and_then(|(_, value)| value.trim().parse::<f64>().ok())
+        // NaN isn't a number of seconds at all
+        .filter(|seconds| !seconds.is_nan())
+        .map(|seconds| Duration::from_secs_f64(seconds.clamp(0.0, MAX_RETRY_AFTER)));
+    Ok((status, retry_after))
+}
@@ -0,0 +1,91 @@
+// just enough WebSocket (RFC 6455) for the event feed: the handshake, and whole frames both ways
+
+use crate::http::Request;
+use sha1::{Digest, Sha1};
+use tokio::io::{self, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+// opcodes
+pub const TEXT: u8 = 0x1;
+pub const CLOSE: u8 = 0x8;
+pub const PING: u8 = 0x9;
+pub const PONG: u8 = 0xA;
+
+// the client only sends control frames, which are tiny, so anything big is a mistake
+const MAX_PAYLOAD: u64 = 64 * 1024;
+
+// appended to the client's key before hashing, from the RFC
+const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+// the client's key, if the request asks for a WebSocket
+pub fn key(request: &Request) -> Option<&str> {
+    let upgrade = request.header("upgrade")?;
+    if !upgrade.eq_ignore_ascii_case("websocket") {
+        return None;
+    }
+    request.header("sec-websocket-key")
+}
+
+// after this, only frames are sent
+pub async fn accept<W: AsyncWrite + Unpin>(stream: &mut W, key: &str) -> io::Result<()> {
+    let mut hasher = Sha1::new();
+    hasher.update(key.as_bytes());
+    hasher.update(GUID.as_bytes());
+    let head = format!(
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        base64::encode(hasher.finalize())
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.flush().await
+}
+
+// returns the opcode and the unmasked payload
+pub async fn read_frame<R: AsyncRead + Unpin>(stream: &mut R) -> io::Result<(u8, Vec<u8>)> {
+    let mut head = [0u8; 2];
+    stream.read_exact(&mut head).await?;
+    let opcode = head[0] & 0x0F;
+    let masked = head[1] & 0x80 != 0;
+    let length = match head[1] & 0x7F {
+        126 => stream.read_u16().await? as u64,
+        127 => stream.read_u64().await?,
+        length => length as u64,
+    };
+    if length > MAX_PAYLOAD {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "The WebSocket frame is too long",
+        ));
+    }
+    let mut mask = [0u8; 4];
+    if masked {
+        stream.read_exact(&mut mask).await?;
+    }
+    let mut payload = vec![0u8; length as usize];
+    stream.read_exact(&mut payload).await?;
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+    Ok((opcode, payload))
+}
+
+// frames from the server aren't masked
+pub async fn write_frame<W: AsyncWrite + Unpin>(
+    stream: &mut W,
+    opcode: u8,
+    payload: &[u8],
+) -> io::Result<()> {
+    let mut frame = vec![0x80 | opcode]; // the final (and only) fragment
+    match payload.len() {
+        length if length < 126 => frame.push(length as u8),
+        length if length <= u16::MAX as usize => {
+            frame.push(126);
+            frame.extend_from_slice(&(length as u16).to_be_bytes());
+        }
+        length => {
+            frame.push(127);
+            frame.extend_from_slice(&(length as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    stream.write_all(&frame).await?;
+    stream.flush().await
+}
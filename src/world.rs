@@ -0,0 +1,396 @@
+// the blocks around the bot, kept up to date from the chunk and block change packets
+// all info available on https://wiki.vg/index.php?title=Chunk_Format&oldid=16165
+
+use crate::blocks;
+use crate::datatypes::*;
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::Arc;
+use tokio::io;
+
+// bits per block when the global palette is used (protocol 754)
+const GLOBAL_PALETTE_BITS: u8 = 15;
+
+// cloning it is cheap, the chunks are only copied when they change while a clone still has them,
+// so that a copy can be searched for paths on another thread
+#[derive(Debug, Default, Clone)]
+pub struct World {
+    chunks: HashMap<(i32, i32), Arc<Chunk>>, // chunk x, z -> chunk
+}
+
+#[derive(Debug, Clone)]
+pub struct Chunk {
+    sections: Vec<Option<Section>>, // 16 sections from the bottom, None if only air
+}
+
+#[derive(Debug, Clone)]
+struct Section {
+    bits: u8,                  // bits per block
+    palette: Option<Vec<u32>>, // None if the global palette is used
+    data: Vec<u64>, // the blocks in y, z, x order, packed so that no block spans two longs
+}
+
+impl World {
+    pub fn load_chunk(&mut self, x: i32, z: i32, full_chunk: bool, chunk: Chunk) {
+        match self.chunks.get_mut(&(x, z)) {
+            Some(old) if !full_chunk => {
+                // only the sent sections changed
+                let old = Arc::make_mut(old);
+                for (old_section, new_section) in old.sections.iter_mut().zip(chunk.sections) {
+                    if new_section.is_some() {
+                        *old_section = new_section;
+                    }
+                }
+            }
+            _ => {
+                self.chunks.insert((x, z), Arc::new(chunk));
+            }
+        }
+    }
+    pub fn unload_chunk(&mut self, x: i32, z: i32) {
+        self.chunks.remove(&(x, z));
+    }
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+    }
+    // the block state at the given position, None if the chunk is not loaded
+    pub fn block(&self, x: i32, y: i32, z: i32) -> Option<u32> {
+        let chunk = self.chunks.get(&(x >> 4, z >> 4))?;
+        if !(0..256).contains(&y) {
+            return Some(0);
+        }
+
+        match &chunk.sections[(y >> 4) as usize] {
+            Some(section) => Some(section.get(index(x, y, z))),
+            None => Some(0),
+        }
+    }
+    // whether there are no solid blocks between the two points
+    // blocks in unloaded chunks are assumed to not be in the way
+    pub fn line_of_sight(&self, from: (f64, f64, f64), to: (f64, f64, f64)) -> bool {
+        let from = [from.0, from.1, from.2];
+        let to = [to.0, to.1, to.2];
+        let end = [
+            to[0].floor() as i32,
+            to[1].floor() as i32,
+            to[2].floor() as i32,
+        ];
+        let mut block = [
+            from[0].floor() as i32,
+            from[1].floor() as i32,
+            from[2].floor() as i32,
+        ];
+
+        // walk through every block that the line crosses, one block boundary at a time
+        // (http://www.cse.yorku.ca/~amana/research/grid.pdf)
+        let mut step = [0; 3];
+        let mut t_max = [f64::INFINITY; 3]; // how far along the line the next boundary on each axis is
+        let mut t_delta = [f64::INFINITY; 3]; // how far along the line one block on each axis is
+        for axis in 0..3 {
+            let d = to[axis] - from[axis];
+            if d > 0.0 {
+                step[axis] = 1;
+                t_max[axis] = (block[axis] as f64 + 1.0 - from[axis]) / d;
+                t_delta[axis] = 1.0 / d;
+            } else if d < 0.0 {
+                step[axis] = -1;
+                t_max[axis] = (block[axis] as f64 - from[axis]) / d;
+                t_delta[axis] = -1.0 / d;
+            }
+        }
+
+        while block != end {
+            let axis = if t_max[0] < t_max[1] && t_max[0] < t_max[2] {
+                0
+            } else if t_max[1] < t_max[2] {
+                1
+            } else {
+                2
+            };
+            if t_max[axis] > 1.0 {
+                break;
+            }
+            block[axis] += step[axis];
+            t_max[axis] += t_delta[axis];
+
+            // the block of the target itself doesn't count
+            if block == end {
+                break;
+            }
+            if let Some(state) = self.block(block[0], block[1], block[2]) {
+                if !blocks::is_passable(state) {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+    pub fn set_block(&mut self, x: i32, y: i32, z: i32, state: u32) {
+        if !(0..256).contains(&y) {
+            return;
+        }
+        if let Some(chunk) = self.chunks.get_mut(&(x >> 4, z >> 4)) {
+            Arc::make_mut(chunk).sections[(y >> 4) as usize]
+                .get_or_insert_with(Section::empty)
+                .set(index(x, y, z), state);
+        }
+    }
+}
+
+impl Chunk {
+    // parses the data array of the chunk data packet
+    pub async fn parse(primary_bit_mask: i64, data: Vec<u8>) -> io::Result<Self> {
+        let mut input = Cursor::new(data);
+        let mut sections = Vec::with_capacity(16);
+
+        for section_y in 0..16 {
+            if primary_bit_mask & (1 << section_y) == 0 {
+                sections.push(None);
+                continue;
+            }
+
+            // number of non-air blocks, not needed
+            i16::deserialize(&mut input).await?;
+            let bits = u8::deserialize(&mut input).await?;
+            let palette = if bits <= 8 {
+                let palette = Vec::<VarInt>::deserialize(&mut input).await?;
+                Some(palette.into_iter().map(|state| state.0 as u32).collect())
+            } else {
+                None
+            };
+            let longs = VarInt::deserialize(&mut input).await?;
+            let mut data = Vec::with_capacity(longs.0 as usize);
+            for _ in 0..longs.0 {
+                data.push(i64::deserialize(&mut input).await? as u64);
+            }
+
+            sections.push(Some(Section {
+                bits,
+                palette,
+                data,
+            }));
+        }
+
+        Ok(Self { sections })
+    }
+}
+
+impl Section {
+    fn empty() -> Self {
+        Self::with_bits(4, Some(vec![0]))
+    }
+    fn with_bits(bits: u8, palette: Option<Vec<u32>>) -> Self {
+        let per_long = 64 / bits as usize;
+        Self {
+            bits,
+            palette,
+            data: vec![0; 4096_usize.div_ceil(per_long)],
+        }
+    }
+    fn get(&self, index: usize) -> u32 {
+        let value = self.get_raw(index);
+        match &self.palette {
+            Some(palette) => palette.get(value as usize).copied().unwrap_or(0),
+            None => value,
+        }
+    }
+    fn set(&mut self, index: usize, state: u32) {
+        let value = match &mut self.palette {
+            Some(palette) => match palette.iter().position(|&s| s == state) {
+                Some(value) => value as u32,
+                None if palette.len() < 1 << self.bits => {
+                    palette.push(state);
+                    palette.len() as u32 - 1
+                }
+                None => {
+                    // the palette is full, so more bits per block are needed
+                    self.grow();
+                    return self.set(index, state);
+                }
+            },
+            None => state,
+        };
+        self.set_raw(index, value);
+    }
+    // repacks all blocks with one more bit per block, or the global palette if too many
+    fn grow(&mut self) {
+        let mut new = if self.bits < 8 {
+            Self::with_bits(self.bits + 1, self.palette.clone())
+        } else {
+            Self::with_bits(GLOBAL_PALETTE_BITS, None)
+        };
+        for index in 0..4096 {
+            new.set(index, self.get(index));
+        }
+        *self = new;
+    }
+    fn get_raw(&self, index: usize) -> u32 {
+        let per_long = 64 / self.bits as usize;
+        let mask = (1u64 << self.bits) - 1;
+        let long = self.data.get(index / per_long).copied().unwrap_or(0);
+
+        ((long >> ((index % per_long) * self.bits as usize)) & mask) as u32
+    }
+    fn set_raw(&mut self, index: usize, value: u32) {
+        let per_long = 64 / self.bits as usize;
+        let mask = (1u64 << self.bits) - 1;
+        let shift = (index % per_long) * self.bits as usize;
+        if let Some(long) = self.data.get_mut(index / per_long) {
+            *long = (*long & !(mask << shift)) | ((value as u64 & mask) << shift);
+        }
+    }
+}
+
+// the index of the block in its chunk section
+fn index(x: i32, y: i32, z: i32) -> usize {
+    (((y & 15) << 8) | ((z & 15) << 4) | (x & 15)) as usize
+}
+
+// x, y, z from the packed position format
+pub fn decode_position(position: i64) -> (i32, i32, i32) {
+    (
+        (position >> 38) as i32,
+        (position << 52 >> 52) as i32,
+        (position << 26 >> 38) as i32,
+    )
+}
+
+// chunk section x, y, z from the packed section position format
+pub fn decode_section_position(position: i64) -> (i32, i32, i32) {
+    (
+        (position >> 42) as i32,
+        (position << 44 >> 44) as i32,
+        (position << 22 >> 42) as i32,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STONE: u32 = 1;
+    const WATER: u32 = 34;
+    const COBWEB: u32 = 1341;
+
+    fn var_int(mut value: u32, output: &mut Vec<u8>) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                output.push(byte);
+                return;
+            }
+            output.push(byte | 0x80);
+        }
+    }
+
+    fn section(output: &mut Vec<u8>, bits: u8, palette: Option<&[u32]>, data: &[u64]) {
+        output.extend_from_slice(&1i16.to_be_bytes());
+        output.push(bits);
+        if let Some(palette) = palette {
+            var_int(palette.len() as u32, output);
+            for &state in palette {
+                var_int(state, output);
+            }
+        }
+        var_int(data.len() as u32, output);
+        for long in data {
+            output.extend_from_slice(&long.to_be_bytes());
+        }
+    }
+
+    // section 0 uses a palette, section 1 the global palette and section 2 isn't sent
+    async fn world() -> World {
+        let mut data = Vec::new();
+
+        let mut longs = vec![0; 256];
+        longs[0] = 1 | 2 << 4; // stone at x 0, water at x 1
+        section(&mut data, 4, Some(&[0, STONE, WATER]), &longs);
+
+        let mut longs = vec![0; 1024];
+        longs[0] = COBWEB as u64; // at y 16
+        longs[1] = (STONE as u64) << 15; // 5 blocks later, at x 5 y 16
+        section(&mut data, GLOBAL_PALETTE_BITS, None, &longs);
+
+        let mut world = World::default();
+        let chunk = Chunk::parse(0b11, data).await.unwrap();
+        world.load_chunk(0, 0, true, chunk);
+        world
+    }
+
+    #[tokio::test]
+    async fn reads_both_palettes() {
+        let world = world().await;
+        assert_eq!(world.block(0, 0, 0), Some(STONE));
+        assert_eq!(world.block(1, 0, 0), Some(WATER));
+        assert_eq!(world.block(2, 0, 0), Some(0));
+        assert_eq!(world.block(0, 16, 0), Some(COBWEB));
+        assert_eq!(world.block(5, 16, 0), Some(STONE));
+        assert_eq!(world.block(4, 16, 0), Some(0));
+    }
+
+    #[tokio::test]
+    async fn empty_sections_and_unloaded_chunks() {
+        let world = world().await;
+        assert_eq!(world.block(0, 40, 0), Some(0));
+        assert_eq!(world.block(0, 300, 0), Some(0));
+        assert_eq!(world.block(16, 0, 0), None);
+        assert_eq!(world.block(-1, 0, 0), None);
+    }
+
+    #[tokio::test]
+    async fn only_sent_sections_replace_the_old_ones() {
+        let mut world = world().await;
+        let mut data = Vec::new();
+        section(&mut data, 4, Some(&[WATER]), &[0; 256]);
+        let chunk = Chunk::parse(0b10, data).await.unwrap();
+        world.load_chunk(0, 0, false, chunk);
+
+        assert_eq!(world.block(0, 0, 0), Some(STONE));
+        assert_eq!(world.block(0, 16, 0), Some(WATER));
+    }
+
+    #[tokio::test]
+    async fn set_block_grows_the_palette() {
+        let mut world = world().await;
+        // more states than fit in 8 bits, so it ends up with the global palette
+        for x in 0..16 {
+            for z in 0..16 {
+                for y in 32..34 {
+                    world.set_block(x, y, z, (x + z * 16 + (y - 32) * 256) as u32);
+                }
+            }
+        }
+        for x in 0..16 {
+            for z in 0..16 {
+                for y in 32..34 {
+                    assert_eq!(
+                        world.block(x, y, z),
+                        Some((x + z * 16 + (y - 32) * 256) as u32)
+                    );
+                }
+            }
+        }
+        // the other sections are left alone
+        assert_eq!(world.block(0, 0, 0), Some(STONE));
+    }
+
+    #[tokio::test]
+    async fn line_of_sight() {
+        let mut world = world().await;
+        assert!(world.line_of_sight((0.5, 2.5, 2.5), (10.5, 2.5, 2.5)));
+        world.set_block(5, 2, 2, STONE);
+        assert!(!world.line_of_sight((0.5, 2.5, 2.5), (10.5, 2.5, 2.5)));
+        // water and cobwebs don't block it
+        world.set_block(5, 2, 2, WATER);
+        world.set_block(6, 2, 2, COBWEB);
+        assert!(world.line_of_sight((0.5, 2.5, 2.5), (10.5, 2.5, 2.5)));
+    }
+
+    #[test]
+    fn positions() {
+        let position = ((-100i64 & 0x3ffffff) << 38) | ((200i64 & 0x3ffffff) << 12) | 64;
+        assert_eq!(decode_position(position), (-100, 64, 200));
+    }
+}